@@ -83,8 +83,8 @@ export default function Kitties (props) {
           attrs={{
             palletRpc: 'kittiesModule',
             callable: 'create',
-            inputParams: [],
-            paramFields: []
+            inputParams: [null],
+            paramFields: [true]
           }}
         />
       </Form.Field>
@@ -1,5 +1,5 @@
 import React, { useEffect, useState } from 'react'
-import { Form, Input, Grid, Card, Statistic } from 'semantic-ui-react'
+import { Form, Input, Grid, Table } from 'semantic-ui-react'
 
 import { useSubstrate } from './substrate-lib'
 import { TxButton } from './substrate-lib/components'
@@ -11,59 +11,99 @@ function Main (props) {
   // The transaction submission status
   const [status, setStatus] = useState('')
 
-  // The currently stored value
-  const [currentValue, setCurrentValue] = useState(0)
-  const [formValue, setFormValue] = useState(0)
+  // The items the selected account stores
+  const [items, setItems] = useState([])
+  const [formState, setFormState] = useState({ key: '', value: '' })
+
+  const onChange = (_, data) =>
+    setFormState(prev => ({ ...prev, [data.state]: data.value }))
+
+  const { key, value } = formState
 
   useEffect(() => {
+    if (!accountPair) {
+      setItems([])
+      return
+    }
+
     let unsubscribe
-    api.query.templateModule.something(newValue => {
-      // The storage value is an Option<u32>
-      // So we have to check whether it is None first
-      // There is also unwrapOr
-      if (newValue.isNone) {
-        setCurrentValue('<None>')
-      } else {
-        setCurrentValue(newValue.unwrap().toNumber())
-      }
+    const address = accountPair.address
+    // Re-read the account's items whenever its item count changes.
+    api.query.templateModule.itemCount(address, () => {
+      api.query.templateModule.items.entries(address)
+        .then(entries => setItems(entries.map(([storageKey, item]) => {
+          const [, itemKey] = storageKey.args
+          const stored = item.unwrap()
+          return {
+            key: itemKey.toUtf8(),
+            value: stored.value.toUtf8(),
+            setAt: stored.set_at.toNumber()
+          }
+        })))
+        .catch(console.error)
     }).then(unsub => {
       unsubscribe = unsub
     })
       .catch(console.error)
 
     return () => unsubscribe && unsubscribe()
-  }, [api.query.templateModule])
+  }, [api.query.templateModule, accountPair, status])
 
   return (
     <Grid.Column width={8}>
       <h1>Template Module</h1>
-      <Card centered>
-        <Card.Content textAlign='center'>
-          <Statistic
-            label='Current Value'
-            value={currentValue}
-          />
-        </Card.Content>
-      </Card>
+      {items.length === 0
+        ? <p>The selected account stores no values.</p>
+        : <Table celled striped size='small'>
+          <Table.Body>
+            {items.map(item =>
+              <Table.Row key={item.key}>
+                <Table.Cell width={4}>{item.key}</Table.Cell>
+                <Table.Cell width={9} style={{ overflowWrap: 'break-word' }}>{item.value}</Table.Cell>
+                <Table.Cell width={3}>#{item.setAt}</Table.Cell>
+              </Table.Row>
+            )}
+          </Table.Body>
+        </Table>}
       <Form>
         <Form.Field>
           <Input
-            label='New Value'
-            state='newValue'
-            type='number'
-            onChange={(_, { value }) => setFormValue(value)}
+            label='Key'
+            state='key'
+            type='text'
+            onChange={onChange}
+          />
+        </Form.Field>
+        <Form.Field>
+          <Input
+            label='Value'
+            state='value'
+            type='text'
+            onChange={onChange}
           />
         </Form.Field>
         <Form.Field style={{ textAlign: 'center' }}>
           <TxButton
             accountPair={accountPair}
-            label='Store Something'
+            label='Set Value'
+            type='SIGNED-TX'
+            setStatus={setStatus}
+            attrs={{
+              palletRpc: 'templateModule',
+              callable: 'setValue',
+              inputParams: [key, value],
+              paramFields: [true, true]
+            }}
+          />
+          <TxButton
+            accountPair={accountPair}
+            label='Remove Value'
             type='SIGNED-TX'
             setStatus={setStatus}
             attrs={{
               palletRpc: 'templateModule',
-              callable: 'doSomething',
-              inputParams: [formValue],
+              callable: 'removeValue',
+              inputParams: [key],
               paramFields: [true]
             }}
           />
@@ -76,7 +116,7 @@ function Main (props) {
 
 export default function TemplateModule (props) {
   const { api } = useSubstrate()
-  return (api.query.templateModule && api.query.templateModule.something
+  return (api.query.templateModule && api.query.templateModule.items
     ? <Main {...props} />
     : null)
 }
@@ -69,7 +69,13 @@ const connect = (state, dispatch) => {
   dispatch({ type: 'CONNECT_INIT' })
 
   const provider = new WsProvider(socket)
-  const _api = new ApiPromise({ provider, types, rpc: jsonrpc })
+  // `FreeProofs` wraps `ChargeTransactionPayment` and encodes the same tip, followed by the free
+  // proof slot the transaction claims. Pass `freeSlot` to `signAndSend` to queue several free
+  // proofs; it defaults to the era's first slot.
+  const signedExtensions = {
+    FreeProofs: { extrinsic: { tip: 'Compact<Balance>', freeSlot: 'Compact<u32>' }, payload: {} }
+  }
+  const _api = new ApiPromise({ provider, types, rpc: jsonrpc, signedExtensions })
 
   // Set listeners for disconnection and reconnection event.
   _api.on('connected', () => {
@@ -0,0 +1,62 @@
+//! Documents registered with `store_document`, read back from the offchain database.
+//!
+//! The runtime writes the chunks of a document through offchain indexing, so they only exist on
+//! nodes started with `--enable-offchain-indexing true` that imported the block.
+
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use node_template_runtime::{pallet_poe::Claim, PoeModule};
+use sp_core::{
+	offchain::{OffchainStorage, STORAGE_PREFIX},
+	Bytes,
+};
+
+/// Document RPC methods.
+#[rpc]
+pub trait PoeDocumentApi {
+	/// The document proven by `claim`, or `None` if this node has none of its chunks.
+	#[rpc(name = "poe_document")]
+	fn document(&self, claim: Claim) -> RpcResult<Option<Bytes>>;
+}
+
+/// Implements [`PoeDocumentApi`] on top of the node's offchain storage.
+pub struct PoeDocument<S> {
+	storage: S,
+}
+
+impl<S> PoeDocument<S> {
+	/// Create new `PoeDocument` reading from `storage`.
+	pub fn new(storage: S) -> Self {
+		PoeDocument { storage }
+	}
+}
+
+impl<S> PoeDocumentApi for PoeDocument<S>
+where
+	S: OffchainStorage + 'static,
+{
+	fn document(&self, claim: Claim) -> RpcResult<Option<Bytes>> {
+		let mut data = Vec::new();
+		let chunks = (0..)
+			.map(|index| self.storage.get(STORAGE_PREFIX, &PoeModule::document_chunk_key(&claim, index)))
+			.take_while(Option::is_some)
+			.flatten();
+		for chunk in chunks {
+			data.extend(chunk);
+		}
+		if data.is_empty() {
+			return Ok(None)
+		}
+
+		// Chunks of a partly indexed or pruned document must not pass for the original.
+		if claim.algorithm.hash(&data) != claim.digest {
+			return Err(RpcError {
+				code: ErrorCode::ServerError(1),
+				message: "Stored chunks do not match the claim.".into(),
+				data: None,
+			})
+		}
+
+		Ok(Some(Bytes(data)))
+	}
+}
@@ -6,6 +6,9 @@ mod chain_spec;
 mod service;
 mod cli;
 mod command;
+mod document;
+mod poe;
+mod receipt;
 mod rpc;
 
 fn main() -> sc_cli::Result<()> {
@@ -0,0 +1,293 @@
+//! `poe` subcommand: hash documents and look up their proofs of existence.
+
+use crate::{
+	cli::Cli,
+	receipt::{self, Receipt},
+	service,
+};
+use codec::Decode;
+use node_template_runtime::{
+	opaque::Block,
+	pallet_poe::{Claim, HashAlgorithm, NamespaceId, ProofInfo, Proofs, TombstoneOf, Tombstones},
+	AccountId, Balance, BlockNumber, Hash, Moment, Runtime,
+};
+use sc_cli::{CliConfiguration, DatabaseParams, SharedParams, SubstrateCli};
+use sc_client_api::{Backend, StorageProvider};
+use sc_service::PartialComponents;
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	crypto::Ss58Codec,
+	ed25519,
+	hexdisplay::HexDisplay,
+	storage::StorageKey,
+};
+use sp_finality_grandpa::AuthorityId;
+use sp_runtime::generic::BlockId;
+use std::{io::Read, path::PathBuf, sync::Arc};
+use structopt::StructOpt;
+
+#[derive(Debug, StructOpt)]
+pub enum PoeCmd {
+	/// Print the digest of a file in the format `create_proof` expects.
+	Hash(HashCmd),
+
+	/// Look up the proof of a file at the finalized head of the local chain database.
+	Verify(VerifyCmd),
+
+	/// Check a receipt returned by `poe_receipt` without contacting a node.
+	CheckReceipt(CheckReceiptCmd),
+}
+
+#[derive(Debug, StructOpt)]
+pub struct HashCmd {
+	/// The document to hash.
+	#[structopt(parse(from_os_str))]
+	pub file: PathBuf,
+
+	/// One of `blake2-256`, `sha2-256` or `keccak-256`.
+	#[structopt(long, default_value = "blake2-256", parse(try_from_str = parse_algorithm))]
+	pub algorithm: HashAlgorithm,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct VerifyCmd {
+	/// The document to look up.
+	#[structopt(parse(from_os_str))]
+	pub file: PathBuf,
+
+	/// One of `blake2-256`, `sha2-256` or `keccak-256`.
+	#[structopt(long, default_value = "blake2-256", parse(try_from_str = parse_algorithm))]
+	pub algorithm: HashAlgorithm,
+
+	/// The namespace the proof was registered in, if any.
+	#[structopt(long)]
+	pub namespace: Option<NamespaceId>,
+
+	/// Read the best block instead of the finalized one. What it shows may still be reverted.
+	#[structopt(long)]
+	pub best: bool,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[structopt(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+#[derive(Debug, StructOpt)]
+pub struct CheckReceiptCmd {
+	/// The receipt, as returned by the `poe_receipt` RPC.
+	#[structopt(parse(from_os_str))]
+	pub receipt: PathBuf,
+
+	/// Genesis hash of the chain the receipt must come from.
+	#[structopt(long)]
+	pub genesis_hash: Hash,
+
+	/// SS58 addresses of the GRANDPA authorities that finalized the receipt's block.
+	#[structopt(long, required = true, use_delimiter = true)]
+	pub authorities: Vec<String>,
+
+	/// Id of that authority set.
+	#[structopt(long, default_value = "0")]
+	pub set_id: u64,
+
+	/// Also check that the receipt is about this document.
+	#[structopt(long, parse(from_os_str))]
+	pub file: Option<PathBuf>,
+}
+
+fn parse_algorithm(s: &str) -> Result<HashAlgorithm, String> {
+	match s {
+		"blake2-256" => Ok(HashAlgorithm::Blake2_256),
+		"sha2-256" => Ok(HashAlgorithm::Sha2_256),
+		"keccak-256" => Ok(HashAlgorithm::Keccak256),
+		_ => Err(format!("unknown hash algorithm `{}`", s)),
+	}
+}
+
+/// Incremental form of [`HashAlgorithm::hash`].
+enum Hasher {
+	Blake2(blake2_rfc::blake2b::Blake2b),
+	Sha2(sha2::Sha256),
+	Keccak(tiny_keccak::Keccak),
+}
+
+impl Hasher {
+	fn new(algorithm: HashAlgorithm) -> Self {
+		match algorithm {
+			HashAlgorithm::Blake2_256 => Hasher::Blake2(blake2_rfc::blake2b::Blake2b::new(32)),
+			HashAlgorithm::Sha2_256 => Hasher::Sha2(<sha2::Sha256 as sha2::Digest>::new()),
+			HashAlgorithm::Keccak256 => Hasher::Keccak(tiny_keccak::Keccak::v256()),
+		}
+	}
+
+	fn update(&mut self, data: &[u8]) {
+		match self {
+			Hasher::Blake2(hasher) => hasher.update(data),
+			Hasher::Sha2(hasher) => sha2::Digest::update(hasher, data),
+			Hasher::Keccak(hasher) => tiny_keccak::Hasher::update(hasher, data),
+		}
+	}
+
+	fn finalize(self) -> [u8; 32] {
+		let mut digest = [0u8; 32];
+		match self {
+			Hasher::Blake2(hasher) => digest.copy_from_slice(hasher.finalize().as_bytes()),
+			Hasher::Sha2(hasher) => digest.copy_from_slice(&sha2::Digest::finalize(hasher)),
+			Hasher::Keccak(hasher) => tiny_keccak::Hasher::finalize(hasher, &mut digest),
+		}
+		digest
+	}
+}
+
+/// The claim on `file`, hashed a buffer at a time so large documents are never held in memory.
+fn hash_file(
+	file: &PathBuf,
+	namespace: Option<NamespaceId>,
+	algorithm: HashAlgorithm,
+) -> sc_cli::Result<Claim> {
+	let mut file = std::fs::File::open(file)?;
+	let mut hasher = Hasher::new(algorithm);
+	let mut buffer = vec![0u8; 64 * 1024];
+	loop {
+		let read = file.read(&mut buffer)?;
+		if read == 0 {
+			break
+		}
+		hasher.update(&buffer[..read]);
+	}
+	Ok(Claim { namespace, algorithm, digest: hasher.finalize() })
+}
+
+impl PoeCmd {
+	pub fn run(&self, cli: &Cli) -> sc_cli::Result<()> {
+		match self {
+			PoeCmd::Hash(cmd) => cmd.run(),
+			PoeCmd::CheckReceipt(cmd) => cmd.run(),
+			PoeCmd::Verify(cmd) => {
+				let runner = cli.create_runner(cmd)?;
+				runner.sync_run(|config| {
+					let PartialComponents { client, .. } = service::new_partial(&config)?;
+					cmd.run(client)
+				})
+			},
+		}
+	}
+}
+
+impl HashCmd {
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let claim = hash_file(&self.file, None, self.algorithm)?;
+		println!("{:?} 0x{}", claim.algorithm, HexDisplay::from(&claim.digest));
+		Ok(())
+	}
+}
+
+impl VerifyCmd {
+	pub fn run<C, BE>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: StorageProvider<Block, BE> + HeaderBackend<Block>,
+		BE: Backend<Block>,
+	{
+		let claim = hash_file(&self.file, self.namespace, self.algorithm)?;
+		let key = StorageKey(Proofs::<Runtime>::hashed_key_for(&claim));
+		let info = client.info();
+		let (kind, number, at) = if self.best {
+			("best", info.best_number, info.best_hash)
+		} else {
+			("finalized", info.finalized_number, info.finalized_hash)
+		};
+		println!("Reading {} block #{} ({})", kind, number, at);
+
+		let tombstone_key = StorageKey(Tombstones::<Runtime>::hashed_key_for(&claim));
+		if let Some(data) = client.storage(&BlockId::Hash(at), &tombstone_key)? {
+			let tombstone = TombstoneOf::<Runtime>::decode(&mut &data.0[..])
+				.map_err(|e| sc_cli::Error::Input(format!("failed to decode tombstone: {}", e)))?;
+			println!(
+				"{} was revoked by {} at block #{} ({:?})",
+				self.file.display(),
+				tombstone.revoked_by,
+				tombstone.block_number,
+				tombstone.reason
+			);
+		}
+
+		match client.storage(&BlockId::Hash(at), &key)? {
+			Some(data) => {
+				let info = <ProofInfo<AccountId, BlockNumber, Balance, Moment>>::decode(&mut &data.0[..])
+					.map_err(|e| sc_cli::Error::Input(format!("failed to decode proof: {}", e)))?;
+				let created_at = match info.created_at {
+					Some(moment) => format!("timestamp {}", moment),
+					None => "timestamp unknown".into(),
+				};
+				println!(
+					"{} is proven by {} since block #{} ({})",
+					self.file.display(),
+					info.owner,
+					info.block_number,
+					created_at
+				);
+			},
+			None => println!("{} has no proof at {} block #{}", self.file.display(), kind, number),
+		}
+
+		Ok(())
+	}
+}
+
+impl CheckReceiptCmd {
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let receipt: Receipt = serde_json::from_slice(&std::fs::read(&self.receipt)?)
+			.map_err(|e| sc_cli::Error::Input(format!("failed to parse receipt: {}", e)))?;
+		if let Some(file) = &self.file {
+			if hash_file(file, receipt.claim.namespace, receipt.claim.algorithm)? != receipt.claim {
+				return Err(sc_cli::Error::Input(format!("receipt is not about {}", file.display())))
+			}
+		}
+
+		let authorities = self
+			.authorities
+			.iter()
+			.map(|address| {
+				ed25519::Public::from_ss58check(address)
+					.map(AuthorityId::from)
+					.map_err(|e| sc_cli::Error::Input(format!("invalid authority {}: {:?}", address, e)))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		let number = receipt.header.number;
+		match receipt::verify(&receipt, self.genesis_hash, self.set_id, &authorities)
+			.map_err(sc_cli::Error::Input)?
+		{
+			Some(info) => println!(
+				"valid: {:?} 0x{} is proven by {} since block #{} (checked at finalized block #{})",
+				receipt.claim.algorithm,
+				HexDisplay::from(&receipt.claim.digest),
+				info.owner,
+				info.block_number,
+				number
+			),
+			None => println!(
+				"valid: {:?} 0x{} had no proof at finalized block #{}",
+				receipt.claim.algorithm,
+				HexDisplay::from(&receipt.claim.digest),
+				number
+			),
+		}
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for VerifyCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,187 @@
+//! Proof receipts that can be checked without trusting a node.
+//!
+//! A receipt bundles the `Proofs` entry of a claim with a storage read proof, the header whose
+//! state root the read proof is rooted in and a GRANDPA justification finalizing that header.
+//! Anyone who knows the chain's authority set can check it offline.
+
+use codec::Decode;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result as RpcResult};
+use jsonrpc_derive::rpc;
+use node_template_runtime::{
+	opaque::{Block, Header},
+	pallet_poe::{Claim, ProofInfo, Proofs},
+	AccountId, Balance, BlockNumber, Hash, Moment, Runtime,
+};
+use sc_client_api::{BlockBackend, ProofProvider};
+use sc_finality_grandpa::GrandpaJustification;
+use serde::{Deserialize, Serialize};
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_finality_grandpa::{AuthorityId, GRANDPA_ENGINE_ID};
+use sp_runtime::{
+	generic::BlockId,
+	traits::{BlakeTwo256, Header as HeaderT},
+};
+use sp_state_machine::{read_proof_check, StorageProof};
+use std::sync::Arc;
+
+/// The proof information a receipt resolves to.
+pub type ProofInfoOf = ProofInfo<AccountId, BlockNumber, Balance, Moment>;
+
+/// Everything needed to check a claim against a finalized block offline.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+	/// The claim the receipt is about.
+	pub claim: Claim,
+	/// Genesis hash of the chain the receipt was taken from.
+	pub genesis_hash: Hash,
+	/// The finalized header the read proof is rooted in.
+	pub header: Header,
+	/// SCALE encoded `Proofs` entry of the claim, if there is one.
+	pub value: Option<Bytes>,
+	/// Trie nodes proving `value` against the header's state root.
+	pub read_proof: Vec<Bytes>,
+	/// SCALE encoded GRANDPA justification of the header.
+	pub justification: Bytes,
+}
+
+/// Checks `receipt` against a known chain and authority set. Returns the proof it attests,
+/// or `None` if it attests that the claim had no proof.
+///
+/// GRANDPA votes do not commit to the genesis hash, so the authority set is what anchors the
+/// receipt; the genesis hash only guards against mixing up chains run by the same authorities.
+pub fn verify(
+	receipt: &Receipt,
+	genesis_hash: Hash,
+	set_id: u64,
+	authorities: &[AuthorityId],
+) -> Result<Option<ProofInfoOf>, String> {
+	if receipt.genesis_hash != genesis_hash {
+		return Err(format!("receipt is for chain {}, expected {}", receipt.genesis_hash, genesis_hash))
+	}
+
+	let voters = finality_grandpa::voter_set::VoterSet::new(
+		authorities.iter().cloned().map(|authority| (authority, 1)),
+	)
+	.ok_or_else(|| "empty authority set".to_string())?;
+	GrandpaJustification::<Block>::decode_and_verify_finalizes(
+		&receipt.justification,
+		(receipt.header.hash(), *receipt.header.number()),
+		set_id,
+		&voters,
+	)
+	.map_err(|e| format!("invalid justification: {}", e))?;
+
+	let key = Proofs::<Runtime>::hashed_key_for(&receipt.claim);
+	let proof = StorageProof::new(receipt.read_proof.iter().map(|node| node.0.clone()));
+	let value = read_proof_check::<BlakeTwo256, _>(*receipt.header.state_root(), proof, &[&key])
+		.map_err(|e| format!("invalid read proof: {}", e))?
+		.remove(&key)
+		.flatten();
+
+	if value.as_deref() != receipt.value.as_ref().map(|value| &value.0[..]) {
+		return Err("value does not match the read proof".into())
+	}
+
+	value
+		.map(|value| ProofInfoOf::decode(&mut &value[..]))
+		.transpose()
+		.map_err(|e| format!("failed to decode proof: {}", e))
+}
+
+/// Receipt RPC methods.
+#[rpc]
+pub trait PoeReceiptApi {
+	/// A receipt for `claim` at the newest finalized block carrying a GRANDPA justification,
+	/// or at `at`, which must carry one. Justifications are only kept every
+	/// `justification_period` blocks, so a new proof may take that long to show up.
+	#[rpc(name = "poe_receipt")]
+	fn receipt(&self, claim: Claim, at: Option<Hash>) -> RpcResult<Receipt>;
+}
+
+/// Implements [`PoeReceiptApi`] on top of a full client.
+pub struct PoeReceipt<C> {
+	client: Arc<C>,
+}
+
+impl<C> PoeReceipt<C> {
+	/// Create new `PoeReceipt` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		PoeReceipt { client }
+	}
+}
+
+fn rpc_error(message: impl Into<String>) -> RpcError {
+	RpcError { code: ErrorCode::ServerError(1), message: message.into(), data: None }
+}
+
+fn client_error(e: impl std::fmt::Debug) -> RpcError {
+	RpcError {
+		code: ErrorCode::ServerError(1),
+		message: "Unable to build the receipt.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C> PoeReceiptApi for PoeReceipt<C>
+where
+	C: HeaderBackend<Block> + BlockBackend<Block> + ProofProvider<Block> + Send + Sync + 'static,
+{
+	fn receipt(&self, claim: Claim, at: Option<Hash>) -> RpcResult<Receipt> {
+		let info = self.client.info();
+		let mut number = match at {
+			Some(hash) => self
+				.client
+				.number(hash)
+				.map_err(client_error)?
+				.ok_or_else(|| rpc_error("unknown block"))?,
+			None => info.finalized_number,
+		};
+		if number > info.finalized_number {
+			return Err(rpc_error("block is not finalized"))
+		}
+
+		let justification = loop {
+			let justification = self
+				.client
+				.justifications(&BlockId::Number(number))
+				.map_err(client_error)?
+				.and_then(|justifications| justifications.into_justification(GRANDPA_ENGINE_ID));
+			match justification {
+				Some(justification) => break justification,
+				None if at.is_some() || number == 0 =>
+					return Err(rpc_error("no GRANDPA justification for the block")),
+				None => number -= 1,
+			}
+		};
+
+		let id = BlockId::Number(number);
+		let header = self
+			.client
+			.header(id)
+			.map_err(client_error)?
+			.ok_or_else(|| rpc_error("unknown block"))?;
+		let key = Proofs::<Runtime>::hashed_key_for(&claim);
+		let proof = self
+			.client
+			.read_proof(&id, &mut std::iter::once(&key[..]))
+			.map_err(client_error)?;
+		let read_proof: Vec<Bytes> = proof.iter_nodes().map(Bytes).collect();
+
+		let nodes = StorageProof::new(read_proof.iter().map(|node| node.0.clone()));
+		let value = read_proof_check::<BlakeTwo256, _>(*header.state_root(), nodes, &[&key])
+			.map_err(client_error)?
+			.remove(&key)
+			.flatten();
+
+		Ok(Receipt {
+			claim,
+			genesis_hash: info.genesis_hash,
+			header,
+			value: value.map(Bytes),
+			read_proof,
+			justification: Bytes(justification),
+		})
+	}
+}
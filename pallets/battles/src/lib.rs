@@ -0,0 +1,329 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Battles between kitties, settled with a stake from each side.
+//!
+//! An accepted challenge is not fought right away. It is settled in `on_initialize`
+//! `SettlementDelay` blocks later, with randomness from that block. When `accept` is submitted,
+//! the block hashes the roll is derived from do not exist yet, so the accepting owner cannot
+//! check the outcome first and only accept fights it would win. Both kitties are frozen and
+//! both stakes reserved until then, so neither side can back out.
+//!
+//! The randomness is still only as good as `T::Randomness`. With
+//! `pallet_randomness_collective_flip` it comes from recent block hashes, so a block author
+//! taking part in a battle can still bias it, e.g. by withholding a block. Stakes should stay
+//! small unless the runtime provides a stronger source, such as BABE's VRF output.
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use frame_support::{
+        pallet_prelude::*,
+        storage::bounded_vec::BoundedVec,
+        traits::{BalanceStatus, Currency, Randomness, ReservableCurrency},
+        transactional,
+    };
+    use frame_system::pallet_prelude::*;
+    use codec::{Encode, Decode};
+    use pallet_kitties::KittyProvider;
+    use sp_io::hashing::blake2_128;
+    use sp_runtime::{traits::Saturating, RuntimeDebug};
+
+    /// Battle stats derived from a kitty's DNA.
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, Default, RuntimeDebug)]
+    pub struct KittyStats {
+        pub attack: u16,
+        pub defense: u16,
+        pub speed: u16,
+    }
+
+    impl KittyStats {
+        pub fn from_dna(dna: &[u8; 16]) -> Self {
+            let sum = |bytes: &[u8]| bytes.iter().map(|b| *b as u16).sum::<u16>();
+            KittyStats {
+                attack: sum(&dna[0..5]),
+                defense: sum(&dna[5..10]),
+                speed: sum(&dna[10..15]),
+            }
+        }
+
+        pub fn power(&self) -> u32 {
+            self.attack as u32 + self.defense as u32 + self.speed as u32
+        }
+    }
+
+    /// A pending challenge issued by the owner of a kitty.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Challenge<AccountId, KittyIndex> {
+        pub challenger: AccountId,
+        pub opponent_kitty: KittyIndex,
+    }
+
+    /// An accepted challenge waiting to be settled.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct Battle<AccountId, KittyIndex> {
+        pub challenger: AccountId,
+        pub opponent: AccountId,
+        pub opponent_kitty: KittyIndex,
+    }
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+        type KittyIndex: Parameter + Member + Copy;
+        type Kitties: KittyProvider<Self::AccountId, Self::KittyIndex>;
+        type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Amount each side puts at stake; the winner takes both.
+        type BattleStake: Get<BalanceOf<Self>>;
+        /// Number of blocks between accepting a challenge and settling the battle. Must be at
+        /// least one, so the roll uses a block hash that did not exist when it was accepted.
+        type SettlementDelay: Get<Self::BlockNumber>;
+        /// Number of battles that can be settled in one block.
+        type MaxBattlesPerBlock: Get<u32>;
+    }
+
+    type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    #[pallet::pallet]
+    #[pallet::generate_store(pub (super) trait Store)]
+    pub struct Pallet<T>(_);
+
+    #[pallet::storage]
+    #[pallet::getter(fn challenges)]
+    pub type Challenges<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        Challenge<T::AccountId, T::KittyIndex>
+    >;
+
+    /// Accepted challenges, by the challenger's kitty.
+    #[pallet::storage]
+    #[pallet::getter(fn battles)]
+    pub type Battles<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        Battle<T::AccountId, T::KittyIndex>
+    >;
+
+    /// The challenger's kitty of every battle to settle at the start of a block.
+    #[pallet::storage]
+    #[pallet::getter(fn battles_due)]
+    pub type BattlesDue<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        BoundedVec<T::KittyIndex, T::MaxBattlesPerBlock>,
+        ValueQuery
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn experience)]
+    pub type Experience<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        u32,
+        ValueQuery
+    >;
+
+    #[pallet::event]
+    #[pallet::metadata(T::AccountId = "AccountId")]
+    #[pallet::generate_deposit(pub (super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// [challenger, kitty, opponent_kitty]
+        ChallengeIssued(T::AccountId, T::KittyIndex, T::KittyIndex),
+        /// [challenger, kitty]
+        ChallengeCancelled(T::AccountId, T::KittyIndex),
+        /// [opponent, kitty, opponent_kitty, settled_at]
+        ChallengeAccepted(T::AccountId, T::KittyIndex, T::KittyIndex, T::BlockNumber),
+        /// [winner, winner_kitty, loser_kitty]
+        BattleWon(T::AccountId, T::KittyIndex, T::KittyIndex),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        InvalidKittyId,
+        NotKittyOwner,
+        SameOwner,
+        KittyFrozen,
+        KittyListed,
+        ChallengeNotExist,
+        /// `MaxBattlesPerBlock` battles are already due in the settlement block.
+        TooManyBattles,
+    }
+
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let due = BattlesDue::<T>::take(now);
+            for kitty_id in due.iter() {
+                Self::settle(*kitty_id);
+            }
+
+            // Per battle: the battle, both kitties' DNA and experience, the randomness, both
+            // stakes and unfreezing both kitties.
+            let count = due.len() as Weight;
+            T::DbWeight::get().reads_writes(1 + count * 8, 1 + count * 6)
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        #[pallet::weight(0)]
+        pub fn challenge(origin: OriginFor<T>, kitty_id: T::KittyIndex, opponent_kitty: T::KittyIndex)
+            -> DispatchResultWithPostInfo {
+            let challenger = ensure_signed(origin)?;
+
+            let owner = T::Kitties::owner_of(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+            ensure!(owner == challenger, Error::<T>::NotKittyOwner);
+            ensure!(!T::Kitties::is_frozen(kitty_id), Error::<T>::KittyFrozen);
+
+            let opponent = T::Kitties::owner_of(opponent_kitty).ok_or(Error::<T>::InvalidKittyId)?;
+            ensure!(opponent != challenger, Error::<T>::SameOwner);
+
+            T::Currency::reserve(&challenger, T::BattleStake::get())?;
+            T::Kitties::set_frozen(kitty_id, true);
+            Challenges::<T>::insert(kitty_id, Challenge {
+                challenger: challenger.clone(),
+                opponent_kitty,
+            });
+
+            Self::deposit_event(Event::ChallengeIssued(challenger, kitty_id, opponent_kitty));
+
+            Ok(().into())
+        }
+
+        #[pallet::weight(0)]
+        pub fn cancel(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let challenge = Self::challenges(kitty_id).ok_or(Error::<T>::ChallengeNotExist)?;
+            ensure!(challenge.challenger == sender, Error::<T>::NotKittyOwner);
+
+            T::Currency::unreserve(&sender, T::BattleStake::get());
+            Self::close_challenge(kitty_id);
+
+            Self::deposit_event(Event::ChallengeCancelled(sender, kitty_id));
+
+            Ok(().into())
+        }
+
+        /// Accepts a challenge against the caller's kitty. The battle is settled
+        /// `SettlementDelay` blocks later.
+        #[pallet::weight(0)]
+        #[transactional]
+        pub fn accept(origin: OriginFor<T>, kitty_id: T::KittyIndex) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let challenge = Self::challenges(kitty_id).ok_or(Error::<T>::ChallengeNotExist)?;
+            let opponent_kitty = challenge.opponent_kitty;
+
+            let opponent = T::Kitties::owner_of(opponent_kitty).ok_or(Error::<T>::InvalidKittyId)?;
+            ensure!(opponent == sender, Error::<T>::NotKittyOwner);
+            // The opponent kitty is not frozen by the challenge, so it may have changed hands or
+            // been put up for sale since.
+            ensure!(sender != challenge.challenger, Error::<T>::SameOwner);
+            ensure!(!T::Kitties::is_frozen(opponent_kitty), Error::<T>::KittyFrozen);
+            ensure!(!T::Kitties::is_listed(opponent_kitty), Error::<T>::KittyListed);
+
+            let settled_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::SettlementDelay::get());
+            BattlesDue::<T>::try_mutate(settled_at, |due| due.try_push(kitty_id))
+                .map_err(|_| Error::<T>::TooManyBattles)?;
+
+            T::Currency::reserve(&sender, T::BattleStake::get())?;
+            // The challenger's kitty stays frozen from the challenge.
+            T::Kitties::set_frozen(opponent_kitty, true);
+            Challenges::<T>::remove(kitty_id);
+            Battles::<T>::insert(kitty_id, Battle {
+                challenger: challenge.challenger,
+                opponent: sender.clone(),
+                opponent_kitty,
+            });
+
+            Self::deposit_event(
+                Event::ChallengeAccepted(sender, kitty_id, opponent_kitty, settled_at)
+            );
+
+            Ok(().into())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        fn close_challenge(kitty_id: T::KittyIndex) {
+            T::Kitties::set_frozen(kitty_id, false);
+            Challenges::<T>::remove(kitty_id);
+        }
+
+        /// Fights the battle of `kitty_id` and releases both kitties. If a kitty has lost its
+        /// DNA, both stakes are returned and nobody wins.
+        fn settle(kitty_id: T::KittyIndex) {
+            let battle = match Battles::<T>::take(kitty_id) {
+                Some(battle) => battle,
+                None => return,
+            };
+            let opponent_kitty = battle.opponent_kitty;
+            T::Kitties::set_frozen(kitty_id, false);
+            T::Kitties::set_frozen(opponent_kitty, false);
+
+            let stake = T::BattleStake::get();
+            let challenger_wins = match Self::challenger_wins(kitty_id, opponent_kitty) {
+                Ok(challenger_wins) => challenger_wins,
+                Err(_) => {
+                    T::Currency::unreserve(&battle.challenger, stake);
+                    T::Currency::unreserve(&battle.opponent, stake);
+                    return;
+                }
+            };
+            let (winner, winner_kitty, loser, loser_kitty) = if challenger_wins {
+                (battle.challenger, kitty_id, battle.opponent, opponent_kitty)
+            } else {
+                (battle.opponent, opponent_kitty, battle.challenger, kitty_id)
+            };
+
+            // The winner holds a reserve, so its account exists and the stake can move.
+            let _ = T::Currency::repatriate_reserved(&loser, &winner, stake, BalanceStatus::Free);
+            T::Currency::unreserve(&winner, stake);
+            Experience::<T>::mutate(winner_kitty, |exp| *exp = exp.saturating_add(1));
+
+            Self::deposit_event(Event::BattleWon(winner, winner_kitty, loser_kitty));
+        }
+
+        pub fn stats(kitty_id: T::KittyIndex) -> Option<KittyStats> {
+            T::Kitties::dna_of(kitty_id).map(|dna| KittyStats::from_dna(&dna))
+        }
+
+        /// Each side scores its DNA power, a bonus for experience and a random roll.
+        /// Ties go to the challenged kitty.
+        fn challenger_wins(kitty_id: T::KittyIndex, opponent_kitty: T::KittyIndex)
+            -> Result<bool, DispatchError> {
+            let stats = Self::stats(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
+            let opponent_stats = Self::stats(opponent_kitty).ok_or(Error::<T>::InvalidKittyId)?;
+
+            let roll = Self::random_value(kitty_id, opponent_kitty);
+            let score = |stats: KittyStats, kitty_id: T::KittyIndex, roll: &[u8]| {
+                let luck = roll.iter().map(|b| *b as u32).sum::<u32>();
+                stats.power() + Self::experience(kitty_id).saturating_mul(16) + luck
+            };
+
+            Ok(score(stats, kitty_id, &roll[0..8]) > score(opponent_stats, opponent_kitty, &roll[8..16]))
+        }
+
+        /// Only called while settling, so the randomness comes from the settlement block.
+        fn random_value(kitty_id: T::KittyIndex, opponent_kitty: T::KittyIndex) -> [u8; 16] {
+            let payload = (
+                T::Randomness::random(b"battle").0,
+                kitty_id,
+                opponent_kitty,
+            );
+            payload.using_encoded(blake2_128)
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate as pallet_battles;
+use frame_support::{parameter_types, traits::Hooks};
+use frame_system as system;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u128;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
+        Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		KittiesModule: pallet_kitties::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
+		BattlesModule: pallet_battles::{Pallet, Call, Storage, Event<T>},
+	}
+);
+
+parameter_types! {
+	pub const BlockHashCount: u64 = 250;
+	pub const SS58Prefix: u8 = 42;
+    pub const ExistentialDeposit: u128 = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const MaxReserves: u32 = 50;
+    pub const PledgeQuantity: u128 = 1;
+    pub const MaxCollectionNameLength: u32 = 16;
+    pub const MaxHistoryLength: u32 = 3;
+    pub const VolumeBucketLength: u64 = 10;
+    pub const VolumeBuckets: u32 = 3;
+    pub const ListingTtl: u64 = 10;
+    pub const UnsignedPriority: u64 = 1 << 20;
+    pub const BattleStake: u128 = 5;
+    pub const SettlementDelay: u64 = 2;
+    pub const MaxBattlesPerBlock: u32 = 1;
+}
+
+impl system::Config for Test {
+    type BaseCallFilter = frame_support::traits::AllowAll;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = BlockHashCount;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = SS58Prefix;
+    type OnSetCode = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = MaxReserves;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = u128;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+impl pallet_kitties::Config for Test {
+    type Event = Event;
+    type Randomness = RandomnessCollectiveFlip;
+    type KittyIndex = u32;
+    type Currency = Balances;
+    type PledgeQuantity = PledgeQuantity;
+    type CollectionId = u32;
+    type MaxCollectionNameLength = MaxCollectionNameLength;
+    type MaxHistoryLength = MaxHistoryLength;
+    type VolumeBucketLength = VolumeBucketLength;
+    type VolumeBuckets = VolumeBuckets;
+    type ListingTtl = ListingTtl;
+    type UnsignedPriority = UnsignedPriority;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test where Call: From<C> {
+    type OverarchingCall = Call;
+    type Extrinsic = UncheckedExtrinsic;
+}
+
+impl pallet_battles::Config for Test {
+    type Event = Event;
+    type KittyIndex = u32;
+    type Kitties = KittiesModule;
+    type Randomness = RandomnessCollectiveFlip;
+    type Currency = Balances;
+    type BattleStake = BattleStake;
+    type SettlementDelay = SettlementDelay;
+    type MaxBattlesPerBlock = MaxBattlesPerBlock;
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 20), (2, 20), (3, 10)],
+	}
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+
+    ext
+}
+
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        BattlesModule::on_initialize(System::block_number());
+    }
+}
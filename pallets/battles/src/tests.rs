@@ -0,0 +1,229 @@
+use crate::{Error, mock::*};
+use super::*;
+use frame_support::{assert_ok, assert_noop};
+use pallet_kitties::KittyProvider;
+
+fn setup_kitties() {
+    assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+    assert_ok!(KittiesModule::create(Origin::signed(2), None)); // kitty_index: 1
+}
+
+#[test]
+fn challenge_works() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+
+        assert_eq!(
+            Challenges::<Test>::get(0),
+            Some(Challenge { challenger: 1, opponent_kitty: 1 })
+        );
+        assert!(KittiesModule::is_frozen(0));
+        assert_eq!(Balances::reserved_balance(1), 6); // pledge + stake
+    })
+}
+
+#[test]
+fn challenge_failed_when_caller_is_not_owner() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_noop!(
+            BattlesModule::challenge(Origin::signed(2), 0, 1),
+            Error::<Test>::NotKittyOwner
+        );
+    })
+}
+
+#[test]
+fn challenge_failed_when_opponent_is_self() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 2
+        assert_noop!(
+            BattlesModule::challenge(Origin::signed(1), 0, 2),
+            Error::<Test>::SameOwner
+        );
+    })
+}
+
+#[test]
+fn challenge_failed_when_kitty_frozen() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_noop!(
+            BattlesModule::challenge(Origin::signed(1), 0, 1),
+            Error::<Test>::KittyFrozen
+        );
+    })
+}
+
+#[test]
+fn cancel_works() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_ok!(BattlesModule::cancel(Origin::signed(1), 0));
+
+        assert_eq!(Challenges::<Test>::get(0), None);
+        assert!(!KittiesModule::is_frozen(0));
+        assert_eq!(Balances::reserved_balance(1), 1);
+    })
+}
+
+#[test]
+fn accept_works() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_ok!(BattlesModule::accept(Origin::signed(2), 0));
+
+        // Nothing is fought until the settlement block.
+        assert_eq!(Challenges::<Test>::get(0), None);
+        assert_eq!(
+            BattlesModule::battles(0),
+            Some(Battle { challenger: 1, opponent: 2, opponent_kitty: 1 })
+        );
+        assert_eq!(BattlesModule::battles_due(3).into_inner(), vec![0]);
+        assert!(KittiesModule::is_frozen(0));
+        assert!(KittiesModule::is_frozen(1));
+        assert_eq!(Balances::reserved_balance(1), 6);
+        assert_eq!(Balances::reserved_balance(2), 6);
+        System::assert_last_event(
+            crate::mock::Event::BattlesModule(crate::Event::ChallengeAccepted(2, 0, 1, 3))
+        );
+    })
+}
+
+#[test]
+fn battle_is_settled_after_the_delay() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_ok!(BattlesModule::accept(Origin::signed(2), 0));
+
+        run_to_block(2);
+        assert!(BattlesModule::battles(0).is_some());
+
+        run_to_block(3);
+        assert_eq!(BattlesModule::battles(0), None);
+        assert!(BattlesModule::battles_due(3).is_empty());
+        assert!(!KittiesModule::is_frozen(0));
+        assert!(!KittiesModule::is_frozen(1));
+        assert_eq!(Balances::reserved_balance(1), 1);
+        assert_eq!(Balances::reserved_balance(2), 1);
+
+        let (winner, winner_kitty, loser) = if BattlesModule::experience(0) == 1 {
+            (1, 0, 2)
+        } else {
+            (2, 1, 1)
+        };
+        assert_eq!(BattlesModule::experience(winner_kitty), 1);
+        assert_eq!(BattlesModule::experience(1 - winner_kitty), 0);
+        assert_eq!(Balances::free_balance(winner), 24); // 20 - 1(pledge) + 5
+        assert_eq!(Balances::free_balance(loser), 14); // 20 - 1(pledge) - 5
+        System::assert_last_event(crate::mock::Event::BattlesModule(
+            crate::Event::BattleWon(winner, winner_kitty, 1 - winner_kitty)
+        ));
+    })
+}
+
+#[test]
+fn accepted_challenge_cannot_be_cancelled() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_ok!(BattlesModule::accept(Origin::signed(2), 0));
+        assert_noop!(
+            BattlesModule::cancel(Origin::signed(1), 0),
+            Error::<Test>::ChallengeNotExist
+        );
+        assert_noop!(
+            KittiesModule::transfer(Origin::signed(2), 3, 1),
+            pallet_kitties::Error::<Test>::KittyFrozen
+        );
+    })
+}
+
+#[test]
+fn accept_failed_when_too_many_battles_due() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(KittiesModule::create(Origin::signed(3), None)); // kitty_index: 2
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 3
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 3, 2));
+        assert_ok!(BattlesModule::accept(Origin::signed(2), 0));
+        assert_noop!(
+            BattlesModule::accept(Origin::signed(3), 3),
+            Error::<Test>::TooManyBattles
+        );
+
+        run_to_block(2);
+        assert_ok!(BattlesModule::accept(Origin::signed(3), 3));
+        assert_eq!(BattlesModule::battles_due(4).into_inner(), vec![3]);
+    })
+}
+
+#[test]
+fn accept_failed_when_caller_is_not_opponent() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_noop!(
+            BattlesModule::accept(Origin::signed(3), 0),
+            Error::<Test>::NotKittyOwner
+        );
+    })
+}
+
+#[test]
+fn accept_failed_when_challenge_not_exist() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_noop!(
+            BattlesModule::accept(Origin::signed(2), 0),
+            Error::<Test>::ChallengeNotExist
+        );
+    })
+}
+
+#[test]
+fn accept_failed_when_challenger_owns_opponent() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_ok!(KittiesModule::transfer(Origin::signed(2), 1, 1));
+        assert_noop!(
+            BattlesModule::accept(Origin::signed(1), 0),
+            Error::<Test>::SameOwner
+        );
+    })
+}
+
+#[test]
+fn accept_failed_when_opponent_kitty_frozen() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(KittiesModule::create(Origin::signed(3), None)); // kitty_index: 2
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_ok!(BattlesModule::challenge(Origin::signed(2), 1, 2));
+        assert_noop!(
+            BattlesModule::accept(Origin::signed(2), 0),
+            Error::<Test>::KittyFrozen
+        );
+    })
+}
+
+#[test]
+fn accept_failed_when_opponent_kitty_listed() {
+    new_test_ext().execute_with(|| {
+        setup_kitties();
+        assert_ok!(BattlesModule::challenge(Origin::signed(1), 0, 1));
+        assert_ok!(KittiesModule::ask(Origin::signed(2), 1, Some(10)));
+        assert_noop!(
+            BattlesModule::accept(Origin::signed(2), 0),
+            Error::<Test>::KittyListed
+        );
+    })
+}
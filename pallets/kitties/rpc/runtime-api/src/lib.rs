@@ -0,0 +1,22 @@
+//! Runtime API definition for the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_kitties::{MarketStats, OwnershipRecord};
+
+sp_api::decl_runtime_apis! {
+    pub trait KittiesApi<KittyIndex, AccountId, Balance, BlockNumber> where
+        KittyIndex: Codec,
+        AccountId: Codec,
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Every recorded owner of a kitty, oldest first.
+        fn provenance(kitty_id: KittyIndex) -> Vec<OwnershipRecord<AccountId, Balance, BlockNumber>>;
+        /// Last sale price, 24h volume, floor price and number of listed kitties.
+        fn market_stats() -> MarketStats<Balance>;
+    }
+}
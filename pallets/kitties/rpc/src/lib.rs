@@ -0,0 +1,93 @@
+//! RPC interface for the kitties pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_kitties_rpc_runtime_api::{
+    KittiesApi as KittiesRuntimeApi, MarketStats, OwnershipRecord,
+};
+
+#[rpc]
+pub trait KittiesApi<BlockHash, KittyIndex, AccountId, Balance, BlockNumber> {
+    #[rpc(name = "kitties_provenance")]
+    fn provenance(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<OwnershipRecord<AccountId, Balance, BlockNumber>>>;
+
+    #[rpc(name = "kitties_marketStats")]
+    fn market_stats(&self, at: Option<BlockHash>) -> Result<MarketStats<Balance>>;
+}
+
+/// A struct that implements the [`KittiesApi`].
+pub struct Kitties<C, B> {
+    client: Arc<C>,
+    _marker: PhantomData<B>,
+}
+
+impl<C, B> Kitties<C, B> {
+    /// Create new `Kitties` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Kitties { client, _marker: Default::default() }
+    }
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+    /// The call to runtime failed.
+    RuntimeError,
+}
+
+impl From<Error> for i64 {
+    fn from(e: Error) -> i64 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(Error::RuntimeError.into()),
+        message: "Unable to query the kitties runtime api.".into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+impl<C, Block, KittyIndex, AccountId, Balance, BlockNumber>
+    KittiesApi<<Block as BlockT>::Hash, KittyIndex, AccountId, Balance, BlockNumber>
+    for Kitties<C, Block>
+where
+    Block: BlockT,
+    C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: KittiesRuntimeApi<Block, KittyIndex, AccountId, Balance, BlockNumber>,
+    KittyIndex: Codec,
+    AccountId: Codec,
+    Balance: Codec,
+    BlockNumber: Codec,
+{
+    fn provenance(
+        &self,
+        kitty_id: KittyIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<OwnershipRecord<AccountId, Balance, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.provenance(&at, kitty_id).map_err(runtime_error)
+    }
+
+    fn market_stats(&self, at: Option<<Block as BlockT>::Hash>) -> Result<MarketStats<Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.market_stats(&at).map_err(runtime_error)
+    }
+}
@@ -7,35 +7,124 @@ mod tests;
 #[cfg(test)]
 mod mock;
 
+pub mod migrations;
+
+/// Access to kitties for pallets that build on top of this one, e.g. battles.
+pub trait KittyProvider<AccountId, KittyIndex> {
+    fn owner_of(kitty_id: KittyIndex) -> Option<AccountId>;
+    fn dna_of(kitty_id: KittyIndex) -> Option<[u8; 16]>;
+    fn is_frozen(kitty_id: KittyIndex) -> bool;
+    fn is_listed(kitty_id: KittyIndex) -> bool;
+    /// A frozen kitty cannot be transferred, listed or sold.
+    fn set_frozen(kitty_id: KittyIndex, frozen: bool);
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
         pallet_prelude::*,
-        traits::{Randomness, ReservableCurrency, Currency, ExistenceRequirement},
+        traits::{
+            Randomness, NamedReservableCurrency, Currency, ExistenceRequirement, StorageVersion,
+        },
         sp_runtime::traits::AtLeast32Bit,
+        storage::bounded_vec::BoundedVec,
+        transactional, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+    };
+    use frame_system::{
+        offchain::{SendTransactionTypes, SubmitTransaction},
+        pallet_prelude::*,
     };
-    use frame_system::pallet_prelude::*;
     use codec::{Encode, Decode};
     use sp_io::hashing::blake2_128;
-    use sp_runtime::traits::Bounded;
+    use sp_runtime::{traits::{Bounded, Saturating, Zero}, RuntimeDebug};
+    use sp_std::{convert::TryFrom, vec::Vec};
 
     #[derive(Encode, Decode)]
     pub struct Kitty(pub [u8; 16]);
 
+    /// A limited drop of kitties registered by a creator.
+    #[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound)]
+    pub struct Collection<AccountId, Balance, MaxNameLength>
+    where
+        AccountId: Clone + PartialEq + Eq + core::fmt::Debug,
+        Balance: Clone + PartialEq + Eq + core::fmt::Debug,
+        MaxNameLength: Get<u32>,
+    {
+        pub creator: AccountId,
+        pub name: BoundedVec<u8, MaxNameLength>,
+        pub max_supply: u32,
+        pub minted: u32,
+        pub price: Balance,
+    }
+
+    /// One entry of a kitty's provenance: who took ownership, for how much and when.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub struct OwnershipRecord<AccountId, Balance, BlockNumber> {
+        pub owner: AccountId,
+        pub price: Option<Balance>,
+        pub block_number: BlockNumber,
+    }
+
+    /// Aggregated figures about the kitty market.
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub struct MarketStats<Balance> {
+        pub last_sale_price: Option<Balance>,
+        pub volume_24h: Balance,
+        pub floor_price: Option<Balance>,
+        pub listed_count: u32,
+    }
+
+    /// Upper bound on clean-up transactions submitted by one offchain worker run.
+    const MAX_PRUNED_LISTINGS_PER_BLOCK: usize = 16;
+
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
+    /// Named reserve holding kitty pledges, apart from whatever else an owner has reserved.
+    pub const PLEDGE_ID: [u8; 8] = *b"kittypld";
+
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
         type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
         type KittyIndex: Parameter + Member + AtLeast32Bit + Default + Copy;
-        type Currency: ReservableCurrency<Self::AccountId>;
+        type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
+        /// Balance reserved for a kitty by whoever takes ownership of it. It may change while
+        /// kitties exist; every kitty keeps the amount its owner actually reserved.
         type PledgeQuantity: Get<BalanceOf<Self>>;
+        type CollectionId: Parameter + Member + AtLeast32Bit + Default + Copy;
+        type MaxCollectionNameLength: Get<u32>;
+        /// Number of ownership records kept per kitty; the oldest are dropped first.
+        type MaxHistoryLength: Get<u32>;
+        /// Sale volume is tracked in buckets of this many blocks.
+        type VolumeBucketLength: Get<Self::BlockNumber>;
+        /// Number of buckets that make up the 24h volume window.
+        type VolumeBuckets: Get<u32>;
+        /// Number of blocks an ask stays valid for; zero means asks never expire.
+        type ListingTtl: Get<Self::BlockNumber>;
+        /// Priority of the unsigned transactions that prune stale listings.
+        type UnsignedPriority: Get<TransactionPriority>;
     }
 
-    type BalanceOf<T> =
+    pub type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+    pub type CollectionOf<T> = Collection<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as Config>::MaxCollectionNameLength,
+    >;
+
+    pub type OwnershipRecordOf<T> = OwnershipRecord<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
     #[pallet::pallet]
     #[pallet::generate_store(pub (super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::storage]
@@ -62,6 +151,28 @@ pub mod pallet {
         ValueQuery
     >;
 
+    /// The pledge the owner of every kitty reserved for it.
+    #[pallet::storage]
+    #[pallet::getter(fn kitty_pledge)]
+    pub type KittyPledges<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        BalanceOf<T>,
+        ValueQuery
+    >;
+
+    /// Sum of the pledges of the kitties every account owns.
+    #[pallet::storage]
+    #[pallet::getter(fn pledged)]
+    pub type Pledged<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+        ValueQuery
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn kitty_price)]
     pub type KittyPrice<T: Config> = StorageMap<
@@ -72,6 +183,85 @@ pub mod pallet {
         ValueQuery
     >;
 
+    #[pallet::storage]
+    #[pallet::getter(fn kitty_listed_at)]
+    pub type KittyListedAt<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        T::BlockNumber
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn listed_count)]
+    pub type ListedCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn last_sale_price)]
+    pub type LastSalePrice<T: Config> = StorageValue<_, BalanceOf<T>>;
+
+    /// Sale volume per bucket, keyed by the first block of the bucket.
+    #[pallet::storage]
+    pub type SaleVolume<T: Config> = StorageValue<
+        _,
+        BoundedVec<(T::BlockNumber, BalanceOf<T>), T::VolumeBuckets>,
+        ValueQuery
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn kitty_history)]
+    pub type KittyHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        BoundedVec<OwnershipRecordOf<T>, T::MaxHistoryLength>,
+        ValueQuery
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn kitty_frozen)]
+    pub type FrozenKitties<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        bool,
+        ValueQuery
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn collections_count)]
+    pub type CollectionsCount<T: Config> = StorageValue<_, T::CollectionId>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn collections)]
+    pub type Collections<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::CollectionId,
+        Option<CollectionOf<T>>,
+        ValueQuery
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn kitty_collection)]
+    pub type KittyCollection<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::KittyIndex,
+        Option<T::CollectionId>,
+        ValueQuery
+    >;
+
+    #[pallet::storage]
+    pub type CollectionKitties<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::CollectionId,
+        Blake2_128Concat,
+        T::KittyIndex,
+        ()
+    >;
+
     #[pallet::event]
     #[pallet::metadata(T::AccountId = "AccountId")]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
@@ -79,7 +269,9 @@ pub mod pallet {
         KittyCreated(T::AccountId, T::KittyIndex),
         KittyTransferred(T::AccountId, T::AccountId, T::KittyIndex),
         KittyAsk(T::KittyIndex, Option<BalanceOf<T>>),
+        StaleListingRemoved(T::KittyIndex),
         KittySold(T::AccountId, T::AccountId, T::KittyIndex, BalanceOf<T>),
+        CollectionCreated(T::AccountId, T::CollectionId),
     }
 
     #[pallet::error]
@@ -90,33 +282,129 @@ pub mod pallet {
         InvalidKittyId,
         KittyNotForSale,
         PriceTooLow,
+        CollectionsCountOverflow,
+        InvalidCollectionId,
+        CollectionNameTooLong,
+        CollectionSoldOut,
+        KittyFrozen,
+        ListingNotStale,
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            crate::migrations::migrate::<T>()
+        }
+
+        fn offchain_worker(_block_number: T::BlockNumber) {
+            let stale = KittyPrice::<T>::iter()
+                .filter(|(_, price)| price.is_some())
+                .map(|(kitty_id, _)| kitty_id)
+                .filter(|kitty_id| Self::is_stale_listing(*kitty_id))
+                .take(MAX_PRUNED_LISTINGS_PER_BLOCK);
+
+            for kitty_id in stale {
+                let call = Call::remove_stale_listing(kitty_id);
+                if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+                    log::warn!(
+                        target: "runtime::kitties",
+                        "failed to submit clean-up of stale listing {:?}",
+                        kitty_id,
+                    );
+                }
+            }
+        }
+    }
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            if let Call::remove_stale_listing(kitty_id) = call {
+                if !Self::is_stale_listing(*kitty_id) {
+                    return InvalidTransaction::Stale.into()
+                }
+
+                ValidTransaction::with_tag_prefix("KittiesStaleListing")
+                    .priority(T::UnsignedPriority::get())
+                    .and_provides(kitty_id)
+                    .longevity(5)
+                    .propagate(true)
+                    .build()
+            } else {
+                InvalidTransaction::Call.into()
+            }
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::weight(0)]
-        pub fn create(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+        #[transactional]
+        pub fn create(origin: OriginFor<T>, collection_id: Option<T::CollectionId>)
+            -> DispatchResultWithPostInfo {
             let owner = ensure_signed(origin)?;
+
+            if let Some(collection_id) = collection_id {
+                Self::mint_from_collection(&owner, collection_id)?;
+            }
+
             let kitty_id = Self::create_kitty(
                 owner.clone(),
                 Self::random_value(&owner)
             )?;
+
+            if let Some(collection_id) = collection_id {
+                KittyCollection::<T>::insert(kitty_id, Some(collection_id));
+                CollectionKitties::<T>::insert(collection_id, kitty_id, ());
+            }
+
             Self::deposit_event(Event::KittyCreated(owner, kitty_id));
 
             Ok(().into())
         }
 
+        #[pallet::weight(0)]
+        pub fn create_collection(
+            origin: OriginFor<T>,
+            name: Vec<u8>,
+            max_supply: u32,
+            price: BalanceOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let creator = ensure_signed(origin)?;
+            let name = BoundedVec::<u8, T::MaxCollectionNameLength>::try_from(name)
+                .map_err(|_| Error::<T>::CollectionNameTooLong)?;
+
+            let collection_id = Self::collections_count().unwrap_or(0_u32.into());
+            ensure!(
+                collection_id != T::CollectionId::max_value(),
+                Error::<T>::CollectionsCountOverflow
+            );
+
+            Collections::<T>::insert(collection_id, Some(Collection {
+                creator: creator.clone(),
+                name,
+                max_supply,
+                minted: 0,
+                price,
+            }));
+            CollectionsCount::<T>::put(collection_id + 1_u32.into());
+
+            Self::deposit_event(Event::CollectionCreated(creator, collection_id));
+
+            Ok(().into())
+        }
+
         #[pallet::weight(0)]
         pub fn transfer(origin: OriginFor<T>, to: T::AccountId, kitty_id: T::KittyIndex)
             -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
             ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
             ensure!(Some(sender.clone()) == KittyOwners::<T>::get(kitty_id), Error::<T>::NotKittyOwner);
+            ensure!(!Self::kitty_frozen(kitty_id), Error::<T>::KittyFrozen);
 
-            Self::do_transfer(sender.clone(), to.clone(), kitty_id)?;
+            Self::do_transfer(sender.clone(), to.clone(), kitty_id, None)?;
 
             Self::deposit_event(
                 Event::KittyTransferred(sender, to, kitty_id)
@@ -159,11 +447,12 @@ pub mod pallet {
 
             ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
             ensure!(KittyOwners::<T>::get(kitty_id) == Some(sender), Error::<T>::NotKittyOwner);
+            ensure!(!Self::kitty_frozen(kitty_id), Error::<T>::KittyFrozen);
 
-            KittyPrice::<T>::mutate_exists(
-                kitty_id,
-                |old_price| *old_price = Some(price.clone())
-            );
+            match price {
+                Some(price) => Self::list(kitty_id, price),
+                None => Self::unlist(kitty_id),
+            }
 
             Self::deposit_event(
               Event::KittyAsk(kitty_id, price)
@@ -173,12 +462,14 @@ pub mod pallet {
         }
 
         #[pallet::weight(0)]
+        #[transactional]
         pub fn buy(origin: OriginFor<T>, kitty_id: T::KittyIndex, price: BalanceOf<T>) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
             let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::InvalidKittyId)?;
             let kitty_price = Self::kitty_price(kitty_id).ok_or(Error::<T>::KittyNotForSale)?;
 
             ensure!(price >= kitty_price, Error::<T>::PriceTooLow);
+            ensure!(!Self::kitty_frozen(kitty_id), Error::<T>::KittyFrozen);
 
             T::Currency::transfer(
                 &sender,
@@ -189,10 +480,11 @@ pub mod pallet {
                 #[cfg(not(test))]
                 ExistenceRequirement::KeepAlive,
             )?;
-            KittyPrice::<T>::remove(kitty_id);
+            Self::unlist(kitty_id);
+            Self::record_sale(kitty_price);
             KittyOwners::<T>::insert(kitty_id, Some(sender.clone()));
 
-            Self::do_transfer(owner.clone(), sender.clone(), kitty_id)?;
+            Self::do_transfer(owner.clone(), sender.clone(), kitty_id, Some(kitty_price))?;
 
             Self::deposit_event(
                 Event::<T>::KittySold(
@@ -205,9 +497,47 @@ pub mod pallet {
 
             Ok(().into())
         }
+
+        #[pallet::weight(0)]
+        pub fn remove_stale_listing(origin: OriginFor<T>, kitty_id: T::KittyIndex)
+            -> DispatchResultWithPostInfo {
+            ensure_none(origin)?;
+            ensure!(Self::is_stale_listing(kitty_id), Error::<T>::ListingNotStale);
+
+            Self::unlist(kitty_id);
+
+            Self::deposit_event(Event::StaleListingRemoved(kitty_id));
+
+            Ok(().into())
+        }
     }
 
     impl<T: Config> Pallet<T> {
+        /// All kitties minted into the given collection.
+        pub fn collection_kitties(collection_id: T::CollectionId) -> Vec<T::KittyIndex> {
+            CollectionKitties::<T>::iter_prefix(collection_id)
+                .map(|(kitty_id, _)| kitty_id)
+                .collect()
+        }
+
+        fn mint_from_collection(minter: &T::AccountId, collection_id: T::CollectionId) -> DispatchResult {
+            let mut collection = Self::collections(collection_id)
+                .ok_or(Error::<T>::InvalidCollectionId)?;
+            ensure!(collection.minted < collection.max_supply, Error::<T>::CollectionSoldOut);
+
+            T::Currency::transfer(
+                minter,
+                &collection.creator,
+                collection.price,
+                ExistenceRequirement::KeepAlive,
+            )?;
+
+            collection.minted += 1;
+            Collections::<T>::insert(collection_id, Some(collection));
+
+            Ok(())
+        }
+
         fn next_kitty_id() -> Result<T::KittyIndex, Error<T>> {
             let kitty_id = Self::kitties_count().unwrap_or(0_u32.into());
             if kitty_id == T::KittyIndex::max_value() {
@@ -216,25 +546,157 @@ pub mod pallet {
             return Ok(kitty_id)
         }
 
-        fn do_transfer(from: T::AccountId, to: T::AccountId, kitty_id: T::KittyIndex) -> DispatchResult {
+        fn do_transfer(
+            from: T::AccountId,
+            to: T::AccountId,
+            kitty_id: T::KittyIndex,
+            price: Option<BalanceOf<T>>,
+        ) -> DispatchResult {
+            // The new owner reserves the current pledge; the old one gets back what it reserved.
             let pledge = T::PledgeQuantity::get();
-            T::Currency::reserve(&to, pledge)?;
-            T::Currency::unreserve(&from, pledge);
+            Self::reserve_pledge(&to, pledge)?;
+            Self::unreserve_pledge(&from, Self::kitty_pledge(kitty_id));
+            KittyPledges::<T>::insert(kitty_id, pledge);
 
             KittyOwners::<T>::insert(kitty_id, Some(to.clone()));
+            Self::record_ownership(kitty_id, to, price);
 
             Ok(())
         }
 
+        fn record_ownership(kitty_id: T::KittyIndex, owner: T::AccountId, price: Option<BalanceOf<T>>) {
+            let record = OwnershipRecord {
+                owner,
+                price,
+                block_number: frame_system::Pallet::<T>::block_number(),
+            };
+
+            KittyHistory::<T>::mutate(kitty_id, |history| {
+                if !history.is_empty() && history.len() >= T::MaxHistoryLength::get() as usize {
+                    history.remove(0);
+                }
+                let _ = history.try_push(record);
+            });
+        }
+
+        fn list(kitty_id: T::KittyIndex, price: BalanceOf<T>) {
+            Self::unlist(kitty_id);
+
+            KittyPrice::<T>::insert(kitty_id, Some(price));
+            KittyListedAt::<T>::insert(kitty_id, frame_system::Pallet::<T>::block_number());
+            ListedCount::<T>::mutate(|count| *count = count.saturating_add(1));
+        }
+
+        fn unlist(kitty_id: T::KittyIndex) {
+            if KittyPrice::<T>::take(kitty_id).is_none() {
+                return
+            }
+            KittyListedAt::<T>::remove(kitty_id);
+
+            ListedCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+        }
+
+        fn reserve_pledge(who: &T::AccountId, pledge: BalanceOf<T>) -> DispatchResult {
+            T::Currency::reserve_named(&PLEDGE_ID, who, pledge)?;
+            Pledged::<T>::mutate(who, |pledged| *pledged = pledged.saturating_add(pledge));
+            Ok(())
+        }
+
+        fn unreserve_pledge(who: &T::AccountId, pledge: BalanceOf<T>) {
+            T::Currency::unreserve_named(&PLEDGE_ID, who, pledge);
+            Pledged::<T>::mutate_exists(who, |pledged| {
+                *pledged = pledged
+                    .map(|pledged| pledged.saturating_sub(pledge))
+                    .filter(|pledged| !pledged.is_zero());
+            });
+        }
+
+        /// A listing is stale when its kitty is frozen, its owner's pledge reserve no longer
+        /// covers the pledges of all their kitties, or the ask has outlived `ListingTtl`.
+        pub fn is_stale_listing(kitty_id: T::KittyIndex) -> bool {
+            if Self::kitty_price(kitty_id).is_none() {
+                return false
+            }
+
+            let owner = match Self::kitty_owner(kitty_id) {
+                Some(owner) => owner,
+                None => return true,
+            };
+            if Self::kitty_frozen(kitty_id) {
+                return true
+            }
+            if T::Currency::reserved_balance_named(&PLEDGE_ID, &owner) < Self::pledged(&owner) {
+                return true
+            }
+
+            let ttl = T::ListingTtl::get();
+            match Self::kitty_listed_at(kitty_id) {
+                Some(listed_at) if !ttl.is_zero() =>
+                    frame_system::Pallet::<T>::block_number() > listed_at.saturating_add(ttl),
+                _ => false,
+            }
+        }
+
+        fn record_sale(price: BalanceOf<T>) {
+            LastSalePrice::<T>::put(price);
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let bucket_length = T::VolumeBucketLength::get();
+            let bucket = if bucket_length.is_zero() { now } else { now - now % bucket_length };
+            let window_start = Self::volume_window_start(now);
+
+            let mut buckets: Vec<_> = SaleVolume::<T>::get().into();
+            buckets.retain(|(start, _)| *start >= window_start);
+            match buckets.last_mut() {
+                Some((start, volume)) if *start == bucket => *volume = volume.saturating_add(price),
+                _ => buckets.push((bucket, price)),
+            }
+            if buckets.len() > T::VolumeBuckets::get() as usize {
+                buckets.remove(0);
+            }
+            if let Ok(buckets) = BoundedVec::try_from(buckets) {
+                SaleVolume::<T>::put(buckets);
+            }
+        }
+
+        fn volume_window_start(now: T::BlockNumber) -> T::BlockNumber {
+            let window = T::VolumeBucketLength::get()
+                .saturating_mul(T::VolumeBuckets::get().into());
+            now.saturating_sub(window)
+        }
+
+        /// Scans every listing for the floor price, so it is only meant for the runtime API.
+        pub fn market_stats() -> MarketStats<BalanceOf<T>> {
+            let window_start = Self::volume_window_start(frame_system::Pallet::<T>::block_number());
+            let volume_24h = SaleVolume::<T>::get()
+                .iter()
+                .filter(|(start, _)| *start >= window_start)
+                .fold(Zero::zero(), |total: BalanceOf<T>, (_, volume)| total.saturating_add(*volume));
+
+            MarketStats {
+                last_sale_price: Self::last_sale_price(),
+                volume_24h,
+                floor_price: KittyPrice::<T>::iter().filter_map(|(_, price)| price).min(),
+                listed_count: Self::listed_count(),
+            }
+        }
+
+        /// Every recorded owner of a kitty, oldest first; the last entry is the current owner.
+        pub fn provenance(kitty_id: T::KittyIndex) -> Vec<OwnershipRecordOf<T>> {
+            Self::kitty_history(kitty_id).to_vec()
+        }
+
         fn create_kitty(owner: T::AccountId, dna: [u8; 16]) -> Result<T::KittyIndex, DispatchError> {
             let kitty_id = Self::next_kitty_id()?;
 
-            let pleage = T::PledgeQuantity::get();
-            T::Currency::reserve(&owner, pleage)?;
+            let pledge = T::PledgeQuantity::get();
+            Self::reserve_pledge(&owner, pledge)?;
 
             Kitties::<T>::insert(kitty_id, Some(Kitty(dna)));
+            KittyPledges::<T>::insert(kitty_id, pledge);
             KittyOwners::<T>::insert(kitty_id, Some(owner.clone()));
             KittiesCount::<T>::put(kitty_id + 1_u32.into());
+            Self::record_ownership(kitty_id, owner, None);
 
             Ok(kitty_id)
         }
@@ -248,4 +710,30 @@ pub mod pallet {
             payload.using_encoded(blake2_128)
         }
     }
+
+    impl<T: Config> crate::KittyProvider<T::AccountId, T::KittyIndex> for Pallet<T> {
+        fn owner_of(kitty_id: T::KittyIndex) -> Option<T::AccountId> {
+            Self::kitty_owner(kitty_id)
+        }
+
+        fn dna_of(kitty_id: T::KittyIndex) -> Option<[u8; 16]> {
+            Self::kitties(kitty_id).map(|kitty| kitty.0)
+        }
+
+        fn is_frozen(kitty_id: T::KittyIndex) -> bool {
+            Self::kitty_frozen(kitty_id)
+        }
+
+        fn is_listed(kitty_id: T::KittyIndex) -> bool {
+            Self::kitty_price(kitty_id).is_some()
+        }
+
+        fn set_frozen(kitty_id: T::KittyIndex, frozen: bool) {
+            if frozen {
+                FrozenKitties::<T>::insert(kitty_id, true);
+            } else {
+                FrozenKitties::<T>::remove(kitty_id);
+            }
+        }
+    }
 }
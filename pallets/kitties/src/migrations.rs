@@ -0,0 +1,142 @@
+//! Storage migrations for the kitties pallet.
+
+use crate::*;
+use frame_support::{
+    traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+    weights::Weight,
+};
+
+/// Runs every migration the on-chain storage version has not seen yet.
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if Pallet::<T>::on_chain_storage_version() < 1 {
+        weight = weight.saturating_add(v1::migrate::<T>());
+        StorageVersion::new(1).put::<Pallet<T>>();
+    }
+
+    if Pallet::<T>::on_chain_storage_version() < 2 {
+        weight = weight.saturating_add(v2::migrate::<T>());
+        StorageVersion::new(2).put::<Pallet<T>>();
+    }
+
+    if Pallet::<T>::on_chain_storage_version() < 3 {
+        weight = weight.saturating_add(v3::migrate::<T>());
+        StorageVersion::new(3).put::<Pallet<T>>();
+    }
+
+    weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+}
+
+/// Version 1 counts the kitties listed before the market statistics existed and starts their
+/// `ListingTtl` at the upgrade.
+pub mod v1 {
+    use super::*;
+    use frame_support::storage::migration::take_storage_value;
+    use sp_std::vec::Vec;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let now = frame_system::Pallet::<T>::block_number();
+        let prices: Vec<_> = KittyPrice::<T>::iter().collect();
+        let reads = prices.len() as Weight;
+
+        let mut listed: u32 = 0;
+        let mut writes: Weight = 0;
+        for (kitty_id, _) in prices.into_iter().filter(|(_, price)| price.is_some()) {
+            listed += 1;
+            if !KittyListedAt::<T>::contains_key(kitty_id) {
+                KittyListedAt::<T>::insert(kitty_id, now);
+                writes += 1;
+            }
+        }
+        ListedCount::<T>::put(listed);
+
+        // The floor price is computed off-chain now.
+        let pallet_name = <Pallet<T> as PalletInfoAccess>::name();
+        take_storage_value::<BalanceOf<T>>(pallet_name.as_bytes(), b"FloorPrice", &[]);
+
+        T::DbWeight::get().reads_writes(reads + listed as Weight + 1, writes + 2)
+    }
+}
+
+/// Version 2 records the pledge of every kitty and moves it to the [`PLEDGE_ID`] named reserve.
+/// Until then the pledge was a constant, so every owner reserved the current `PledgeQuantity`.
+pub mod v2 {
+    use super::*;
+    use frame_support::traits::{NamedReservableCurrency, ReservableCurrency};
+    use sp_runtime::traits::Saturating;
+
+    pub fn migrate<T: Config>() -> Weight {
+        let pledge = T::PledgeQuantity::get();
+        let mut count: Weight = 0;
+        for (kitty_id, owner) in KittyOwners::<T>::iter() {
+            let owner = match owner {
+                Some(owner) => owner,
+                None => continue,
+            };
+            T::Currency::unreserve(&owner, pledge);
+            if T::Currency::reserve_named(&PLEDGE_ID, &owner, pledge).is_err() {
+                log::warn!(
+                    target: "runtime::kitties",
+                    "failed to move the pledge of kitty {:?} to the named reserve",
+                    kitty_id,
+                );
+            }
+            KittyPledges::<T>::insert(kitty_id, pledge);
+            Pledged::<T>::mutate(&owner, |pledged| *pledged = pledged.saturating_add(pledge));
+            count += 1;
+        }
+
+        // Reading and writing the account and its reserves, the pledge and the owner's total.
+        T::DbWeight::get().reads_writes(count.saturating_mul(4), count.saturating_mul(4))
+    }
+}
+
+/// Version 3 bounds collection names by `MaxCollectionNameLength`. `create_collection` already
+/// refused longer names, but the limit may have been lowered since; such names are truncated.
+pub mod v3 {
+    use super::*;
+    use codec::{Decode, Encode};
+    use frame_support::storage::bounded_vec::BoundedVec;
+    use sp_std::{convert::TryFrom, vec::Vec};
+
+    #[derive(Encode, Decode)]
+    struct OldCollection<AccountId, Balance> {
+        creator: AccountId,
+        name: Vec<u8>,
+        max_supply: u32,
+        minted: u32,
+        price: Balance,
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        let max = T::MaxCollectionNameLength::get() as usize;
+        let mut count: Weight = 0;
+        Collections::<T>::translate::<Option<OldCollection<T::AccountId, BalanceOf<T>>>, _>(
+            |collection_id, old| {
+                count += 1;
+                let mut old = old?;
+                if old.name.len() > max {
+                    log::warn!(
+                        target: "runtime::kitties",
+                        "truncating the name of collection {:?} to {} bytes",
+                        collection_id,
+                        max,
+                    );
+                    old.name.truncate(max);
+                }
+                // Cannot fail, the name was truncated to the bound.
+                let name = BoundedVec::try_from(old.name).ok()?;
+                Some(Some(Collection {
+                    creator: old.creator,
+                    name,
+                    max_supply: old.max_supply,
+                    minted: old.minted,
+                    price: old.price,
+                }))
+            },
+        );
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+}
@@ -7,7 +7,7 @@ use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
 };
 
-type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+pub type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 type Balance = u128;
 
@@ -21,7 +21,7 @@ frame_support::construct_runtime!(
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
         RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
         Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		KittiesModule: pallet_kitties::{Pallet, Call, Storage, Event<T>},
+		KittiesModule: pallet_kitties::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
 	}
 );
 
@@ -30,7 +30,14 @@ parameter_types! {
 	pub const SS58Prefix: u8 = 42;
     pub const ExistentialDeposit: u128 = 1;
     pub const MaxLocks: u32 = 50;
-    pub const PledgeQuantity: u128 = 1;
+    pub const MaxReserves: u32 = 50;
+    pub static PledgeQuantity: u128 = 1;
+    pub const MaxCollectionNameLength: u32 = 16;
+    pub const MaxHistoryLength: u32 = 3;
+    pub const VolumeBucketLength: u64 = 10;
+    pub const VolumeBuckets: u32 = 3;
+    pub const ListingTtl: u64 = 10;
+    pub const UnsignedPriority: u64 = 1 << 20;
 }
 
 impl system::Config for Test {
@@ -63,7 +70,7 @@ impl pallet_randomness_collective_flip::Config for Test {}
 
 impl pallet_balances::Config for Test {
     type MaxLocks = MaxLocks;
-    type MaxReserves = ();
+    type MaxReserves = MaxReserves;
     type ReserveIdentifier = [u8; 8];
     type Balance = u128;
     type Event = Event;
@@ -79,6 +86,18 @@ impl pallet_kitties::Config for Test {
     type KittyIndex = u32;
     type Currency = Balances;
     type PledgeQuantity = PledgeQuantity;
+    type CollectionId = u32;
+    type MaxCollectionNameLength = MaxCollectionNameLength;
+    type MaxHistoryLength = MaxHistoryLength;
+    type VolumeBucketLength = VolumeBucketLength;
+    type VolumeBuckets = VolumeBuckets;
+    type ListingTtl = ListingTtl;
+    type UnsignedPriority = UnsignedPriority;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test where Call: From<C> {
+    type OverarchingCall = Call;
+    type Extrinsic = UncheckedExtrinsic;
 }
 
 // Build genesis storage according to the mock runtime.
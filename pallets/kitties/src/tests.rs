@@ -1,12 +1,23 @@
 use crate::{Error, mock::*};
 use super::*;
-use frame_support::{assert_ok, assert_noop};
+use codec::{Decode, Encode};
+use frame_support::{
+    assert_ok, assert_noop,
+    storage::bounded_vec::BoundedVec,
+    traits::{NamedReservableCurrency, ReservableCurrency},
+    Blake2_128Concat, StorageHasher,
+};
+use sp_std::convert::TryFrom;
+use sp_runtime::{
+    traits::ValidateUnsigned,
+    transaction_validity::{InvalidTransaction, TransactionSource},
+};
 
 #[test]
 fn create_works() {
     new_test_ext().execute_with(|| {
         assert_eq!(Balances::reserved_balance(1), 0);
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_eq!(KittiesModule::kitties_count().unwrap(), 1);
         assert_eq!(KittyOwners::<Test>::get(0).unwrap(), 1);
         assert_eq!(Balances::reserved_balance(1), 1);
@@ -19,7 +30,7 @@ fn create_failed_when_kitty_index_overflow() {
         KittiesCount::<Test>::put(u32::MAX);
 
         assert_noop!(
-            KittiesModule::create(Origin::signed(1)),
+            KittiesModule::create(Origin::signed(1), None),
             Error::<Test>::KittiesCountOverflow
         );
     })
@@ -29,7 +40,7 @@ fn create_failed_when_kitty_index_overflow() {
 fn create_kitty_failed_when_not_enough_funds_pledged() {
     new_test_ext().execute_with(|| {
         assert_noop!(
-            KittiesModule::create(Origin::signed(6)),
+            KittiesModule::create(Origin::signed(6), None),
             pallet_balances::Error::<Test>::InsufficientBalance
         );
     })
@@ -40,7 +51,7 @@ fn transfer_works() {
     new_test_ext().execute_with(|| {
         assert_eq!(Balances::reserved_balance(1), 0);
         assert_eq!(Balances::reserved_balance(2), 0);
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_eq!(Balances::reserved_balance(1), 1);
         assert_eq!(KittyOwners::<Test>::get(0).unwrap(), 1);
         assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
@@ -50,10 +61,49 @@ fn transfer_works() {
     })
 }
 
+#[test]
+fn transfer_returns_the_pledge_the_owner_reserved() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        PledgeQuantity::set(2);
+        assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::reserved_balance(2), 2);
+        assert_eq!(KittiesModule::kitty_pledge(0), 2);
+
+        PledgeQuantity::set(1);
+        assert_ok!(KittiesModule::transfer(Origin::signed(2), 3, 0));
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(Balances::reserved_balance(3), 1);
+    })
+}
+
+#[test]
+fn migration_records_existing_pledges() {
+    new_test_ext().execute_with(|| {
+        use frame_support::traits::StorageVersion;
+
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        // Before version 2 the pledge sat in the anonymous reserve and was not recorded.
+        Balances::unreserve_named(&PLEDGE_ID, &1, 1);
+        assert_ok!(Balances::reserve(&1, 1));
+        KittyPledges::<Test>::remove(0);
+        Pledged::<Test>::remove(1);
+        StorageVersion::new(1).put::<KittiesModule>();
+
+        crate::migrations::migrate::<Test>();
+
+        assert_eq!(KittiesModule::kitty_pledge(0), 1);
+        assert_eq!(KittiesModule::pledged(1), 1);
+        assert_eq!(Balances::reserved_balance_named(&PLEDGE_ID, &1), 1);
+        assert_eq!(StorageVersion::get::<KittiesModule>(), 3);
+    })
+}
+
 #[test]
 fn transfer_failed_when_caller_is_not_owner() {
     new_test_ext().execute_with(|| {
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_noop!(
             KittiesModule::transfer(Origin::signed(2), 3, 0),
             Error::<Test>::NotKittyOwner
@@ -65,7 +115,7 @@ fn transfer_failed_when_caller_is_not_owner() {
 fn transfer_failed_when_recipient_dont_have_enougth_funds() {
     new_test_ext().execute_with(|| {
         assert_eq!(Balances::reserved_balance(1), 0);
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_eq!(Balances::reserved_balance(1), 1);
         assert_noop!(
             KittiesModule::transfer(Origin::signed(1), 6, 0),
@@ -78,8 +128,8 @@ fn transfer_failed_when_recipient_dont_have_enougth_funds() {
 fn breed_works() {
     new_test_ext().execute_with(|| {
         assert_eq!(Balances::reserved_balance(1), 0);
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 1
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 1
         assert_eq!(Balances::reserved_balance(1), 2);
         assert_ok!(KittiesModule::breed(Origin::signed(1), 0, 1)); // kitty_index: 2
         assert_eq!(Balances::reserved_balance(1), 3);
@@ -90,7 +140,7 @@ fn breed_works() {
 #[test]
 fn breed_failed_when_kitty_not_exist() {
     new_test_ext().execute_with(|| {
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_noop!(
             KittiesModule::breed(Origin::signed(1), 1, 2),
             Error::<Test>::InvalidKittyId
@@ -101,7 +151,7 @@ fn breed_failed_when_kitty_not_exist() {
 #[test]
 fn breed_failed_when_parents_are_same() {
     new_test_ext().execute_with(|| {
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_noop!(
             KittiesModule::breed(Origin::signed(1), 1, 1),
             Error::<Test>::SameKitties
@@ -113,7 +163,7 @@ fn breed_failed_when_parents_are_same() {
 fn ask_works() {
     new_test_ext().execute_with(|| {
         assert_eq!(Balances::reserved_balance(1), 0);
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_eq!(Balances::reserved_balance(1), 1);
         assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(10)));
         assert_eq!(KittyPrice::<Test>::get(0), Some(10));
@@ -133,7 +183,7 @@ fn ask_failed_when_kitty_not_exist() {
 #[test]
 fn ask_failed_when_caller_is_not_owner() {
     new_test_ext().execute_with(|| {
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_noop!(
             KittiesModule::ask(Origin::signed(2), 0, Some(10)),
             Error::<Test>::NotKittyOwner
@@ -149,7 +199,7 @@ fn buy_works() {
         assert_eq!(Balances::free_balance(1), 20);
         assert_eq!(Balances::free_balance(2), 20);
 
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_eq!(Balances::reserved_balance(1), 1);
         assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(1)));
         assert_eq!(KittyOwners::<Test>::get(0), Some(1));
@@ -177,7 +227,7 @@ fn buy_failed_when_kitty_not_exist() {
 #[test]
 fn buy_failed_when_kitty_not_for_sale() {
     new_test_ext().execute_with(|| {
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_noop!(
             KittiesModule::buy(Origin::signed(2), 0, 1),
             Error::<Test>::KittyNotForSale
@@ -188,7 +238,7 @@ fn buy_failed_when_kitty_not_for_sale() {
 #[test]
 fn buy_failed_when_not_enough_funds () {
     new_test_ext().execute_with(|| {
-        assert_ok!(KittiesModule::create(Origin::signed(1))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
         assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(1)));
         assert_noop!(
             KittiesModule::buy(Origin::signed(6), 0, 1),
@@ -196,3 +246,339 @@ fn buy_failed_when_not_enough_funds () {
         );
     })
 }
+
+#[test]
+fn create_collection_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create_collection(Origin::signed(1), b"drop".to_vec(), 2, 5));
+        assert_eq!(KittiesModule::collections_count().unwrap(), 1);
+        assert_eq!(
+            KittiesModule::collections(0),
+            Some(Collection {
+                creator: 1,
+                name: BoundedVec::try_from(b"drop".to_vec()).unwrap(),
+                max_supply: 2,
+                minted: 0,
+                price: 5,
+            })
+        );
+    })
+}
+
+#[test]
+fn create_collection_failed_when_name_too_long() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            KittiesModule::create_collection(Origin::signed(1), vec![0; 17], 2, 5),
+            Error::<Test>::CollectionNameTooLong
+        );
+    })
+}
+
+#[test]
+fn migration_bounds_collection_names() {
+    new_test_ext().execute_with(|| {
+        use frame_support::{storage::migration::put_storage_value, traits::StorageVersion};
+
+        // Names were unbounded vectors before version 3, possibly longer than the current limit.
+        for (collection_id, name) in vec![(0u32, b"drop".to_vec()), (1u32, vec![7; 20])] {
+            let old = Some((1u64, name, 2u32, 0u32, 5u128));
+            let key = Blake2_128Concat::hash(&collection_id.encode());
+            put_storage_value(b"KittiesModule", b"Collections", &key, old);
+        }
+        StorageVersion::new(2).put::<KittiesModule>();
+
+        crate::migrations::migrate::<Test>();
+
+        assert_eq!(KittiesModule::collections(0).unwrap().name.into_inner(), b"drop".to_vec());
+        let collection = KittiesModule::collections(1).unwrap();
+        assert_eq!(collection.name.into_inner(), vec![7; 16]);
+        assert_eq!((collection.creator, collection.max_supply, collection.price), (1, 2, 5));
+        assert_eq!(StorageVersion::get::<KittiesModule>(), 3);
+    })
+}
+
+#[test]
+fn create_in_collection_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create_collection(Origin::signed(1), b"drop".to_vec(), 2, 5));
+        assert_ok!(KittiesModule::create(Origin::signed(2), Some(0))); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(2), None)); // kitty_index: 1
+
+        assert_eq!(Balances::free_balance(1), 25);
+        assert_eq!(Balances::free_balance(2), 13); // 20 - 5 - 2(reserved)
+        assert_eq!(KittiesModule::collections(0).unwrap().minted, 1);
+        assert_eq!(KittiesModule::kitty_collection(0), Some(0));
+        assert_eq!(KittiesModule::kitty_collection(1), None);
+        assert_eq!(KittiesModule::collection_kitties(0), vec![0]);
+    })
+}
+
+#[test]
+fn create_in_collection_failed_when_collection_not_exist() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            KittiesModule::create(Origin::signed(1), Some(0)),
+            Error::<Test>::InvalidCollectionId
+        );
+    })
+}
+
+#[test]
+fn create_in_collection_failed_when_sold_out() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create_collection(Origin::signed(1), b"drop".to_vec(), 1, 5));
+        assert_ok!(KittiesModule::create(Origin::signed(2), Some(0))); // kitty_index: 0
+        assert_noop!(
+            KittiesModule::create(Origin::signed(3), Some(0)),
+            Error::<Test>::CollectionSoldOut
+        );
+    })
+}
+
+#[test]
+fn create_in_collection_failed_when_not_enough_funds() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create_collection(Origin::signed(1), b"drop".to_vec(), 1, 5));
+        assert_noop!(
+            KittiesModule::create(Origin::signed(6), Some(0)),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+        assert_eq!(KittiesModule::collections(0).unwrap().minted, 0);
+    })
+}
+
+#[test]
+fn frozen_kitty_cannot_be_transferred_or_sold() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(1)));
+        <KittiesModule as crate::KittyProvider<_, _>>::set_frozen(0, true);
+
+        assert_noop!(
+            KittiesModule::transfer(Origin::signed(1), 2, 0),
+            Error::<Test>::KittyFrozen
+        );
+        assert_noop!(
+            KittiesModule::ask(Origin::signed(1), 0, Some(2)),
+            Error::<Test>::KittyFrozen
+        );
+        assert_noop!(
+            KittiesModule::buy(Origin::signed(2), 0, 1),
+            Error::<Test>::KittyFrozen
+        );
+
+        <KittiesModule as crate::KittyProvider<_, _>>::set_frozen(0, false);
+        assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+    })
+}
+
+#[test]
+fn ownership_history_is_recorded() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        System::set_block_number(2);
+        assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+        System::set_block_number(3);
+        assert_ok!(KittiesModule::ask(Origin::signed(2), 0, Some(4)));
+        assert_ok!(KittiesModule::buy(Origin::signed(3), 0, 4));
+
+        assert_eq!(
+            KittiesModule::provenance(0),
+            vec![
+                OwnershipRecord { owner: 1, price: None, block_number: 1 },
+                OwnershipRecord { owner: 2, price: None, block_number: 2 },
+                OwnershipRecord { owner: 3, price: Some(4), block_number: 3 },
+            ]
+        );
+    })
+}
+
+#[test]
+fn ownership_history_drops_oldest_records() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::transfer(Origin::signed(1), 2, 0));
+        assert_ok!(KittiesModule::transfer(Origin::signed(2), 3, 0));
+        assert_ok!(KittiesModule::transfer(Origin::signed(3), 4, 0));
+
+        let owners: Vec<u64> = KittiesModule::provenance(0).into_iter().map(|r| r.owner).collect();
+        assert_eq!(owners, vec![2, 3, 4]);
+    })
+}
+
+#[test]
+fn market_stats_track_listings() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 1
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 1, Some(3)));
+        assert_eq!(KittiesModule::listed_count(), 2);
+        assert_eq!(KittiesModule::market_stats().floor_price, Some(3));
+
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 1, None));
+        assert_eq!(KittiesModule::listed_count(), 1);
+        assert_eq!(KittiesModule::market_stats().floor_price, Some(5));
+
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(4)));
+        assert_eq!(KittiesModule::listed_count(), 1);
+        assert_eq!(KittiesModule::market_stats().floor_price, Some(4));
+    })
+}
+
+#[test]
+fn migration_counts_existing_listings() {
+    new_test_ext().execute_with(|| {
+        use frame_support::traits::StorageVersion;
+
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 1
+        // Listed before `ListedCount` and `KittyListedAt` existed.
+        KittyPrice::<Test>::insert(0, Some(5));
+        KittyPrice::<Test>::insert(1, Some(3));
+        frame_support::storage::migration::put_storage_value(b"KittiesModule", b"FloorPrice", &[], 3u128);
+        System::set_block_number(4);
+
+        crate::migrations::migrate::<Test>();
+
+        assert_eq!(KittiesModule::listed_count(), 2);
+        assert_eq!(KittiesModule::kitty_listed_at(0), Some(4));
+        assert_eq!(KittiesModule::kitty_listed_at(1), Some(4));
+        assert!(frame_support::storage::migration::get_storage_value::<u128>(
+            b"KittiesModule",
+            b"FloorPrice",
+            &[],
+        ).is_none());
+        assert_eq!(StorageVersion::get::<KittiesModule>(), 3);
+    })
+}
+
+#[test]
+fn market_stats_track_sales() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(2)));
+        assert_ok!(KittiesModule::buy(Origin::signed(2), 0, 2));
+        System::set_block_number(15);
+        assert_ok!(KittiesModule::ask(Origin::signed(2), 0, Some(3)));
+        assert_ok!(KittiesModule::buy(Origin::signed(1), 0, 3));
+
+        assert_eq!(
+            KittiesModule::market_stats(),
+            MarketStats { last_sale_price: Some(3), volume_24h: 5, floor_price: None, listed_count: 0 }
+        );
+
+        // Buckets are 10 blocks long and the window covers 3 of them.
+        System::set_block_number(35);
+        assert_eq!(KittiesModule::market_stats().volume_24h, 3);
+        System::set_block_number(45);
+        assert_eq!(KittiesModule::market_stats().volume_24h, 0);
+    })
+}
+
+#[test]
+fn expired_listing_is_stale() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+        assert!(!KittiesModule::is_stale_listing(0));
+
+        System::set_block_number(12);
+        assert!(KittiesModule::is_stale_listing(0));
+    })
+}
+
+#[test]
+fn listing_without_pledge_or_frozen_is_stale() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(2), None)); // kitty_index: 1
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+        assert_ok!(KittiesModule::ask(Origin::signed(2), 1, Some(5)));
+
+        Balances::unreserve_named(&PLEDGE_ID, &1, 1);
+        <KittiesModule as crate::KittyProvider<_, _>>::set_frozen(1, true);
+
+        assert!(KittiesModule::is_stale_listing(0));
+        assert!(KittiesModule::is_stale_listing(1));
+    })
+}
+
+#[test]
+fn other_reserves_do_not_cover_a_lost_pledge() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 1
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+        assert_ok!(Balances::reserve(&1, 5));
+        assert_eq!(KittiesModule::pledged(1), 2);
+
+        Balances::unreserve_named(&PLEDGE_ID, &1, 1);
+
+        assert!(Balances::reserved_balance(1) > KittiesModule::pledged(1));
+        assert!(KittiesModule::is_stale_listing(0));
+    })
+}
+
+#[test]
+fn remove_stale_listing_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+        System::set_block_number(12);
+
+        assert_ok!(KittiesModule::remove_stale_listing(Origin::none(), 0));
+        assert_eq!(KittiesModule::kitty_price(0), None);
+        assert_eq!(KittiesModule::listed_count(), 0);
+    })
+}
+
+#[test]
+fn remove_stale_listing_failed_when_listing_is_valid() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+
+        assert_noop!(
+            KittiesModule::remove_stale_listing(Origin::none(), 0),
+            Error::<Test>::ListingNotStale
+        );
+        assert_eq!(
+            <KittiesModule as ValidateUnsigned>::validate_unsigned(
+                TransactionSource::External,
+                &crate::Call::remove_stale_listing(0),
+            ),
+            InvalidTransaction::Stale.into()
+        );
+    })
+}
+
+#[test]
+fn offchain_worker_submits_stale_listing_removal() {
+    use frame_support::traits::OffchainWorker;
+    use sp_core::offchain::{testing::TestTransactionPoolExt, TransactionPoolExt};
+
+    let mut ext = new_test_ext();
+    let (pool, state) = TestTransactionPoolExt::new();
+    ext.register_extension(TransactionPoolExt::new(pool));
+
+    ext.execute_with(|| {
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 0
+        assert_ok!(KittiesModule::create(Origin::signed(1), None)); // kitty_index: 1
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 0, Some(5)));
+        System::set_block_number(12);
+        assert_ok!(KittiesModule::ask(Origin::signed(1), 1, Some(5)));
+
+        KittiesModule::offchain_worker(12);
+
+        let txs = state.read().transactions.clone();
+        assert_eq!(txs.len(), 1);
+        let tx = crate::mock::UncheckedExtrinsic::decode(&mut &*txs[0]).unwrap();
+        assert_eq!(tx.signature, None);
+        assert_eq!(
+            tx.function,
+            crate::mock::Call::KittiesModule(crate::Call::remove_stale_listing(0))
+        );
+    });
+}
@@ -0,0 +1,40 @@
+//! Runtime API definition for the proof of existence pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_poe::{Claim, HashAlgorithm, NamespaceId, ProofInfo};
+
+sp_api::decl_runtime_apis! {
+    pub trait PoeApi<AccountId, BlockNumber, Balance, Moment, Signature> where
+        AccountId: Codec,
+        BlockNumber: Codec,
+        Balance: Codec,
+        Moment: Codec,
+        Signature: Codec,
+    {
+        fn get_proof(claim: Claim) -> Option<ProofInfo<AccountId, BlockNumber, Balance, Moment>>;
+        /// One page of the proofs `owner` holds.
+        fn proofs_of(owner: AccountId, page: u32) -> Vec<(Claim, ProofInfo<AccountId, BlockNumber, Balance, Moment>)>;
+        /// Number of proofs registered.
+        fn count() -> u32;
+        /// The proof of the batch rooted at `root` if the document digest `leaf` is included in
+        /// it. Leaves are the digests hashed behind a `0x00` byte. Parents are the sorted pair
+        /// of their children hashed behind a `0x01` byte, and an odd last node is paired with
+        /// itself. `path` holds the sibling of every node from the leaf up to the root. It must
+        /// be exactly as long as the tree over the batch is deep, i.e. `ceil(log2(leaf_count))`.
+        fn verify_inclusion(
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            root: [u8; 32],
+            leaf: [u8; 32],
+            path: Vec<[u8; 32]>,
+        ) -> Option<ProofInfo<AccountId, BlockNumber, Balance, Moment>>;
+        /// Whether `signature` signs the `create_signed_proof` payload of `claim` created by
+        /// `owner`, the SCALE encoding of `(b"poe/signed", genesis_hash, owner, claim)`, with the
+        /// key stored with its proof.
+        fn check_signature(claim: Claim, owner: AccountId, signature: Signature) -> bool;
+    }
+}
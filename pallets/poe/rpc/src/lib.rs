@@ -0,0 +1,160 @@
+//! RPC interface for the proof of existence pallet.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+pub use pallet_poe_rpc_runtime_api::{Claim, HashAlgorithm, NamespaceId, PoeApi as PoeRuntimeApi, ProofInfo};
+
+#[rpc]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber, Balance, Moment, Signature> {
+    #[rpc(name = "poe_getProof")]
+    fn get_proof(
+        &self,
+        claim: Claim,
+        at: Option<BlockHash>,
+    ) -> Result<Option<ProofInfo<AccountId, BlockNumber, Balance, Moment>>>;
+
+    #[rpc(name = "poe_proofsOf")]
+    fn proofs_of(
+        &self,
+        owner: AccountId,
+        page: u32,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<(Claim, ProofInfo<AccountId, BlockNumber, Balance, Moment>)>>;
+
+    #[rpc(name = "poe_count")]
+    fn count(&self, at: Option<BlockHash>) -> Result<u32>;
+
+    #[rpc(name = "poe_verifyInclusion")]
+    fn verify_inclusion(
+        &self,
+        namespace: Option<NamespaceId>,
+        algorithm: HashAlgorithm,
+        root: [u8; 32],
+        leaf: [u8; 32],
+        path: Vec<[u8; 32]>,
+        at: Option<BlockHash>,
+    ) -> Result<Option<ProofInfo<AccountId, BlockNumber, Balance, Moment>>>;
+
+    #[rpc(name = "poe_checkSignature")]
+    fn check_signature(
+        &self,
+        claim: Claim,
+        owner: AccountId,
+        signature: Signature,
+        at: Option<BlockHash>,
+    ) -> Result<bool>;
+}
+
+/// A struct that implements the [`PoeApi`].
+pub struct Poe<C, B> {
+    client: Arc<C>,
+    _marker: PhantomData<B>,
+}
+
+impl<C, B> Poe<C, B> {
+    /// Create new `Poe` with the given reference to the client.
+    pub fn new(client: Arc<C>) -> Self {
+        Poe { client, _marker: Default::default() }
+    }
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+    /// The call to runtime failed.
+    RuntimeError,
+}
+
+impl From<Error> for i64 {
+    fn from(e: Error) -> i64 {
+        match e {
+            Error::RuntimeError => 1,
+        }
+    }
+}
+
+fn runtime_error(e: impl std::fmt::Debug) -> RpcError {
+    RpcError {
+        code: ErrorCode::ServerError(Error::RuntimeError.into()),
+        message: "Unable to query the poe runtime api.".into(),
+        data: Some(format!("{:?}", e).into()),
+    }
+}
+
+impl<C, Block, AccountId, BlockNumber, Balance, Moment, Signature>
+    PoeApi<<Block as BlockT>::Hash, AccountId, BlockNumber, Balance, Moment, Signature>
+    for Poe<C, Block>
+where
+    Block: BlockT,
+    C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+    C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber, Balance, Moment, Signature>,
+    AccountId: Codec,
+    BlockNumber: Codec,
+    Balance: Codec,
+    Moment: Codec,
+    Signature: Codec,
+{
+    fn get_proof(
+        &self,
+        claim: Claim,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<ProofInfo<AccountId, BlockNumber, Balance, Moment>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.get_proof(&at, claim).map_err(runtime_error)
+    }
+
+    fn proofs_of(
+        &self,
+        owner: AccountId,
+        page: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<(Claim, ProofInfo<AccountId, BlockNumber, Balance, Moment>)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.proofs_of(&at, owner, page).map_err(runtime_error)
+    }
+
+    fn count(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.count(&at).map_err(runtime_error)
+    }
+
+    fn verify_inclusion(
+        &self,
+        namespace: Option<NamespaceId>,
+        algorithm: HashAlgorithm,
+        root: [u8; 32],
+        leaf: [u8; 32],
+        path: Vec<[u8; 32]>,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<ProofInfo<AccountId, BlockNumber, Balance, Moment>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.verify_inclusion(&at, namespace, algorithm, root, leaf, path).map_err(runtime_error)
+    }
+
+    fn check_signature(
+        &self,
+        claim: Claim,
+        owner: AccountId,
+        signature: Signature,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<bool> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+        api.check_signature(&at, claim, owner, signature).map_err(runtime_error)
+    }
+}
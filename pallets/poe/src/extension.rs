@@ -0,0 +1,142 @@
+//! A signed extension that lets accounts create a few proofs per era without paying fees.
+
+use crate::{Call, Config, FreeProofsUsed};
+use codec::{Decode, Encode};
+use frame_support::traits::{Get, IsSubType};
+use sp_runtime::{
+    traits::{CheckedDiv, DispatchInfoOf, PostDispatchInfoOf, SignedExtension},
+    transaction_validity::{
+        InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+    },
+    DispatchResult,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// Wraps the fee-charging extension `S`. A `create_proof` call skips `S` while its signer has
+/// free proofs left in the current era of `FreeProofEraLength` blocks; every other call, and
+/// every `create_proof` past the quota, goes through `S` and pays as usual.
+///
+/// The second field is the slot the signer claims: how many free proofs they will have used in
+/// the era before this one. A transaction for slot `n` requires the one for slot `n - 1`, so an
+/// account can queue its whole quota in the pool, and each is included in order. A
+/// `create_proof` claiming a slot that is already used, or past the quota, pays.
+#[derive(Encode, Decode, Clone, Eq, PartialEq)]
+pub struct FreeProofs<T, S>(pub S, #[codec(compact)] pub u32, PhantomData<T>);
+
+impl<T, S> FreeProofs<T, S> {
+    /// Wrap `inner`, typically `ChargeTransactionPayment`, claiming free slot `slot`.
+    pub fn new(inner: S, slot: u32) -> Self {
+        Self(inner, slot, PhantomData)
+    }
+}
+
+impl<T, S: sp_std::fmt::Debug> sp_std::fmt::Debug for FreeProofs<T, S> {
+    #[cfg(feature = "std")]
+    fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        write!(f, "FreeProofs({:?}, {})", self.0, self.1)
+    }
+    #[cfg(not(feature = "std"))]
+    fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+        Ok(())
+    }
+}
+
+/// The current era and the free proofs `who` used in it, if `call` may go without fees in free
+/// slot `slot` now or once the slots before it are used.
+fn free_slot<T: Config>(
+    who: &T::AccountId,
+    call: &<T as frame_system::Config>::Call,
+    slot: u32,
+) -> Option<(T::BlockNumber, u32)>
+where
+    <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+{
+    if !matches!(call.is_sub_type(), Some(Call::create_proof(..))) {
+        return None
+    }
+
+    let now = frame_system::Pallet::<T>::block_number();
+    let era = now.checked_div(&T::FreeProofEraLength::get())?;
+    let (last_era, used) = FreeProofsUsed::<T>::get(who);
+    let used = if last_era == era { used } else { 0 };
+
+    if used <= slot && slot < T::FreeProofsPerEra::get() {
+        Some((era, used))
+    } else {
+        None
+    }
+}
+
+impl<T, S> SignedExtension for FreeProofs<T, S>
+where
+    T: Config + Send + Sync,
+    <T as frame_system::Config>::Call: IsSubType<Call<T>>,
+    S: SignedExtension<AccountId = T::AccountId, Call = <T as frame_system::Config>::Call>,
+{
+    const IDENTIFIER: &'static str = "FreeProofs";
+    type AccountId = T::AccountId;
+    type Call = <T as frame_system::Config>::Call;
+    type AdditionalSigned = S::AdditionalSigned;
+    /// `None` for free transactions.
+    type Pre = Option<S::Pre>;
+
+    fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+        self.0.additional_signed()
+    }
+
+    fn validate(
+        &self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> TransactionValidity {
+        match free_slot::<T>(who, call, self.1) {
+            Some((era, used)) => {
+                let slot = self.1;
+                let requires = if slot > used {
+                    vec![(b"poe/free", who, era, slot - 1).encode()]
+                } else {
+                    vec![]
+                };
+                Ok(ValidTransaction {
+                    requires,
+                    provides: vec![(b"poe/free", who, era, slot).encode()],
+                    ..Default::default()
+                })
+            },
+            None => self.0.validate(who, call, info, len),
+        }
+    }
+
+    fn pre_dispatch(
+        self,
+        who: &Self::AccountId,
+        call: &Self::Call,
+        info: &DispatchInfoOf<Self::Call>,
+        len: usize,
+    ) -> Result<Self::Pre, TransactionValidityError> {
+        match free_slot::<T>(who, call, self.1) {
+            Some((_, used)) if used < self.1 => Err(InvalidTransaction::Future.into()),
+            // Counted even if the call fails, or failing calls would be free to repeat.
+            Some((era, used)) => {
+                FreeProofsUsed::<T>::insert(who, (era, used + 1));
+                Ok(None)
+            },
+            None => self.0.pre_dispatch(who, call, info, len).map(Some),
+        }
+    }
+
+    fn post_dispatch(
+        pre: Self::Pre,
+        info: &DispatchInfoOf<Self::Call>,
+        post_info: &PostDispatchInfoOf<Self::Call>,
+        len: usize,
+        result: &DispatchResult,
+    ) -> Result<(), TransactionValidityError> {
+        match pre {
+            Some(pre) => S::post_dispatch(pre, info, post_info, len, result),
+            None => Ok(()),
+        }
+    }
+}
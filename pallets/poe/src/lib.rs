@@ -1,5 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub use extension::FreeProofs;
 pub use pallet::*;
 
 #[cfg(test)]
@@ -7,20 +8,294 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod extension;
+pub mod migrations;
+
 #[frame_support::pallet]
 pub mod pallet {
-    use frame_support::{dispatch::DispatchResultWithPostInfo, pallet_prelude::*};
+    use frame_support::{
+        dispatch::DispatchResultWithPostInfo,
+        CloneNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+        pallet_prelude::*,
+        storage::bounded_vec::BoundedVec,
+        traits::{
+            Currency, ExistenceRequirement, OnUnbalanced, ReservableCurrency, StorageVersion, Time,
+            WithdrawReasons,
+        },
+        transactional,
+    };
     use frame_system::pallet_prelude::*;
+    use codec::{Encode, Decode, MaxEncodedLen};
+    use sp_runtime::{
+        traits::{IdentifyAccount, Saturating, Verify, Zero},
+        RuntimeDebug,
+    };
     use sp_std::vec::Vec;
 
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
+
+    /// Number of proofs per page returned by [`Pallet::proofs_of`].
+    pub const PROOFS_PAGE_SIZE: usize = 32;
+
+    /// Size of the pieces a stored document is split into in offchain storage.
+    pub const DOCUMENT_CHUNK_SIZE: usize = 16 * 1024;
+
+    /// Weight of hashing one byte of a stored document. Matches the per-byte cost of
+    /// `frame_system::remark_with_event`, which hashes its remark with blake2 as well.
+    const DOCUMENT_WEIGHT_PER_BYTE: Weight = 2_000;
+
+    /// The hash function a claim's digest was computed with.
+    #[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub enum HashAlgorithm {
+        Blake2_256,
+        Sha2_256,
+        Keccak256,
+    }
+
+    impl HashAlgorithm {
+        pub fn hash(&self, data: &[u8]) -> [u8; 32] {
+            match self {
+                HashAlgorithm::Blake2_256 => sp_io::hashing::blake2_256(data),
+                HashAlgorithm::Sha2_256 => sp_io::hashing::sha2_256(data),
+                HashAlgorithm::Keccak256 => sp_io::hashing::keccak_256(data),
+            }
+        }
+
+        /// The leaf of a batch tree for a document digest: the hash of the digest behind a
+        /// `0x00` byte.
+        pub fn hash_leaf(&self, digest: &[u8; 32]) -> [u8; 32] {
+            let mut data = [0u8; 33];
+            data[1..].copy_from_slice(digest);
+            self.hash(&data)
+        }
+
+        /// The parent of two batch tree nodes: the hash of the pair, smaller first, behind a
+        /// `0x01` byte. Sorting means a path needs no left/right flags, and the prefix keeps any
+        /// node from passing for a leaf.
+        pub fn hash_pair(&self, a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+            let (first, second) = if a <= b { (a, b) } else { (b, a) };
+            let mut data = [0u8; 65];
+            data[0] = 1;
+            data[1..33].copy_from_slice(first);
+            data[33..].copy_from_slice(second);
+            self.hash(&data)
+        }
+
+        /// The root of the batch tree over `digests`, or `None` if there are none.
+        ///
+        /// Every digest becomes a leaf with [`Self::hash_leaf`]. Each level then pairs
+        /// neighbouring nodes with [`Self::hash_pair`], and an odd last node is paired with
+        /// itself. Every leaf thus sits exactly [`batch_depth`] levels below the root.
+        pub fn merkle_root(&self, digests: &[[u8; 32]]) -> Option<[u8; 32]> {
+            let mut level: Vec<[u8; 32]> =
+                digests.iter().map(|digest| self.hash_leaf(digest)).collect();
+            while level.len() > 1 {
+                level = level
+                    .chunks(2)
+                    .map(|pair| self.hash_pair(&pair[0], pair.get(1).unwrap_or(&pair[0])))
+                    .collect();
+            }
+            level.pop()
+        }
+    }
+
+    /// Depth of the batch tree over `leaf_count` digests, which is the length of every path
+    /// through it. See [`HashAlgorithm::merkle_root`].
+    pub fn batch_depth(leaf_count: u32) -> u32 {
+        match leaf_count {
+            0 => 0,
+            count => 32 - (count - 1).leading_zeros(),
+        }
+    }
+
+    /// Identifies a namespace registered with `register_namespace`.
+    pub type NamespaceId = u32;
+
+    /// A document identified by its digest. The algorithm is part of the key, so equal digests
+    /// produced by different algorithms are different claims.
+    #[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub struct Claim {
+        /// The namespace the claim is unique in. `None` is the global namespace.
+        pub namespace: Option<NamespaceId>,
+        pub algorithm: HashAlgorithm,
+        pub digest: [u8; 32],
+    }
+
+    /// What is stored for every proof.
+    #[derive(Encode, Decode, MaxEncodedLen, Clone, PartialEq, Eq, RuntimeDebug)]
+    #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+    pub struct ProofInfo<AccountId, BlockNumber, Balance, Moment> {
+        pub owner: AccountId,
+        /// The block the proof was created in. Transfers leave it untouched.
+        pub block_number: BlockNumber,
+        /// The timestamp of the block the proof was created in. `None` for proofs created before
+        /// timestamps were kept, whose creation time is unknown.
+        pub created_at: Option<Moment>,
+        /// The account the storage deposit is reserved from.
+        pub depositor: AccountId,
+        pub deposit: Balance,
+    }
+
+    /// Optional description of the proven document. Each field holds at most `MaxLength` bytes.
+    #[derive(
+        Encode, Decode, MaxEncodedLen, CloneNoBound, PartialEqNoBound, EqNoBound, DefaultNoBound,
+        RuntimeDebugNoBound,
+    )]
+    pub struct ProofMetadata<MaxLength: Get<u32>> {
+        pub title: BoundedVec<u8, MaxLength>,
+        pub mime_type: BoundedVec<u8, MaxLength>,
+        /// Where the document itself can be found.
+        pub uri: BoundedVec<u8, MaxLength>,
+    }
+
+    /// An organisation whose members can register proofs in its own namespace.
+    #[derive(
+        Encode, Decode, MaxEncodedLen, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    )]
+    pub struct NamespaceInfo<AccountId, Balance, MaxMembers>
+    where
+        AccountId: Clone + PartialEq + Eq + core::fmt::Debug,
+        Balance: Clone + PartialEq + Eq + core::fmt::Debug,
+        MaxMembers: Get<u32>,
+    {
+        /// Manages the members. Counts as a member itself.
+        pub owner: AccountId,
+        pub members: BoundedVec<AccountId, MaxMembers>,
+        pub deposit: Balance,
+    }
+
+    /// Why a proof was revoked.
+    #[derive(
+        Encode, Decode, MaxEncodedLen, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    )]
+    pub enum RevocationReason<MaxLength: Get<u32>> {
+        /// A newer version of the document replaces it.
+        Superseded,
+        /// The document or the owner's key leaked.
+        Compromised,
+        /// The proof was registered by mistake.
+        Erroneous,
+        /// Free text of at most `MaxLength` bytes.
+        Other(BoundedVec<u8, MaxLength>),
+    }
+
+    /// What is left of a revoked proof. `revoked_by` reserves `deposit` for as long as it is
+    /// kept.
+    #[derive(
+        Encode, Decode, MaxEncodedLen, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+    )]
+    pub struct Tombstone<AccountId, BlockNumber, Balance, MaxLength>
+    where
+        AccountId: Clone + PartialEq + Eq + core::fmt::Debug,
+        BlockNumber: Clone + PartialEq + Eq + core::fmt::Debug,
+        Balance: Clone + PartialEq + Eq + core::fmt::Debug,
+        MaxLength: Get<u32>,
+    {
+        pub revoked_by: AccountId,
+        pub block_number: BlockNumber,
+        pub reason: RevocationReason<MaxLength>,
+        pub deposit: Balance,
+    }
+
+    /// One hand-over of a proof.
+    #[derive(Encode, Decode, MaxEncodedLen, Clone, PartialEq, Eq, RuntimeDebug)]
+    pub struct TransferRecord<AccountId, BlockNumber> {
+        pub from: AccountId,
+        pub to: AccountId,
+        pub block_number: BlockNumber,
+    }
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
-        type ProofLimit: Get<u32>;
+        type Currency: ReservableCurrency<Self::AccountId>;
+        /// Deposit reserved per byte a proof occupies in storage.
+        type DepositPerByte: Get<BalanceOf<Self>>;
+        /// Whether `accept_proof_transfer` moves the deposit to the new owner. Otherwise it stays
+        /// reserved from the original payer until the proof is revoked.
+        type DepositMovesWithProof: Get<bool>;
+        /// Source of the creation timestamp.
+        type Time: Time;
+        /// Number of transfers kept per proof; the oldest is dropped first.
+        type MaxHistoryLength: Get<u32>;
+        /// Maximum length of each metadata field.
+        type MaxMetadataLength: Get<u32>;
+        /// Maximum number of proofs expiring in the same block. Bounds the work done in
+        /// `on_initialize`.
+        type MaxExpiringPerBlock: Get<u32>;
+        /// Maximum number of attesters, and of required attesters, per proof.
+        type MaxAttesters: Get<u32>;
+        /// Signature a key holder authorises a relayed `create_proof_for` with.
+        type OffchainSignature: Verify<Signer = Self::SigningPublicKey> + Parameter;
+        type SigningPublicKey: IdentifyAccount<AccountId = Self::AccountId> + Parameter;
+        /// Maximum number of members of a namespace, besides its owner.
+        type MaxNamespaceMembers: Get<u32>;
+        /// Maximum length of a free-text revocation reason.
+        type MaxReasonLength: Get<u32>;
+        /// Whether a revoked claim can be registered again. Doing so removes its tombstone.
+        type AllowReregistration: Get<bool>;
+        /// Maximum size of a document passed to `store_document`.
+        type MaxDocumentSize: Get<u32>;
+        /// Fee charged per byte of a stored document, on top of the proof deposit.
+        type DocumentFeePerByte: Get<BalanceOf<Self>>;
+        /// Receives document fees.
+        type DocumentFee: OnUnbalanced<NegativeImbalanceOf<Self>>;
+        /// Number of `create_proof` calls an account can submit without fees per era. See
+        /// [`FreeProofs`](crate::FreeProofs).
+        type FreeProofsPerEra: Get<u32>;
+        /// Length of a free proof era in blocks.
+        type FreeProofEraLength: Get<Self::BlockNumber>;
     }
 
+    pub type BalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    pub type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+        <T as frame_system::Config>::AccountId,
+    >>::NegativeImbalance;
+
+    pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+
+    pub type ProofInfoOf<T> = ProofInfo<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        BalanceOf<T>,
+        MomentOf<T>,
+    >;
+
+    pub type ProofMetadataOf<T> = ProofMetadata<<T as Config>::MaxMetadataLength>;
+
+    pub type NamespaceInfoOf<T> = NamespaceInfo<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        <T as Config>::MaxNamespaceMembers,
+    >;
+
+    pub type RevocationReasonOf<T> = RevocationReason<<T as Config>::MaxReasonLength>;
+
+    pub type TombstoneOf<T> = Tombstone<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        BalanceOf<T>,
+        <T as Config>::MaxReasonLength,
+    >;
+
+    pub type AttestationOf<T> = (
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+    );
+
+    pub type TransferRecordOf<T> = TransferRecord<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
     #[pallet::pallet]
     #[pallet::generate_store(pub (super) trait Store)]
+    #[pallet::generate_storage_info]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     #[pallet::storage]
@@ -28,17 +303,207 @@ pub mod pallet {
     pub type Proofs<T: Config> = StorageMap<
         _,
         Blake2_128Concat,
-        Vec<u8>,
-        (T::AccountId, T::BlockNumber)
+        Claim,
+        ProofInfoOf<T>
+    >;
+
+    /// The era each account last created proofs without fees in, and how many. A count from an
+    /// earlier era is treated as zero and overwritten by the account's next free proof.
+    #[pallet::storage]
+    #[pallet::getter(fn free_proofs_used)]
+    pub type FreeProofsUsed<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        (T::BlockNumber, u32),
+        ValueQuery
+    >;
+
+    /// Size of the documents kept in offchain storage. Only nodes that run with offchain
+    /// indexing enabled have the chunks.
+    #[pallet::storage]
+    #[pallet::getter(fn documents)]
+    pub type Documents<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        u32
+    >;
+
+    /// The account whose key signed the document of a signed proof.
+    #[pallet::storage]
+    #[pallet::getter(fn signers)]
+    pub type Signers<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        T::AccountId
+    >;
+
+    /// The account each proof is offered to.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_transfers)]
+    pub type PendingTransfers<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        T::AccountId
+    >;
+
+    /// Revoked claims. Expired proofs leave no tombstone.
+    #[pallet::storage]
+    #[pallet::getter(fn tombstones)]
+    pub type Tombstones<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        TombstoneOf<T>
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn namespaces)]
+    pub type Namespaces<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        NamespaceId,
+        NamespaceInfoOf<T>
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn next_namespace_id)]
+    pub type NextNamespaceId<T: Config> = StorageValue<_, NamespaceId, ValueQuery>;
+
+    /// Index of the claims each account owns.
+    #[pallet::storage]
+    pub type ProofsByOwner<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        Blake2_128Concat,
+        Claim,
+        ()
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn proof_count)]
+    pub type ProofCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    #[pallet::storage]
+    #[pallet::getter(fn metadata)]
+    pub type Metadata<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        ProofMetadataOf<T>
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn proof_history)]
+    pub type ProofHistory<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        BoundedVec<TransferRecordOf<T>, T::MaxHistoryLength>,
+        ValueQuery
+    >;
+
+    /// Claims whose digest is the Merkle root of a batch of documents, with the batch size.
+    #[pallet::storage]
+    #[pallet::getter(fn batches)]
+    pub type Batches<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        u32
+    >;
+
+    /// Required attesters that have endorsed a proof, with the block they attested in.
+    #[pallet::storage]
+    #[pallet::getter(fn attestations)]
+    pub type Attestations<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        BoundedVec<AttestationOf<T>, T::MaxAttesters>,
+        ValueQuery
+    >;
+
+    /// Accounts the owner requires to attest before the proof is complete.
+    #[pallet::storage]
+    #[pallet::getter(fn required_attesters)]
+    pub type RequiredAttesters<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        BoundedVec<T::AccountId, T::MaxAttesters>,
+        ValueQuery
+    >;
+
+    /// The nonce the next `create_proof_for` signed by an account must carry.
+    #[pallet::storage]
+    #[pallet::getter(fn signer_nonce)]
+    pub type SignerNonces<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        u64,
+        ValueQuery
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn expiry)]
+    pub type Expiry<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Claim,
+        T::BlockNumber
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn expiring)]
+    pub type Expiring<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        BoundedVec<Claim, T::MaxExpiringPerBlock>,
+        ValueQuery
     >;
 
     #[pallet::event]
     #[pallet::metadata(T::AccountId = "AccountId")]
     #[pallet::generate_deposit(pub (super) fn deposit_event)]
     pub enum Event<T: Config> {
-        ProofCreated(T::AccountId, Vec<u8>),
-        ProofRevoked(T::AccountId, Vec<u8>),
-        ProofTransferred(T::AccountId, Vec<u8>),
+        ProofCreated(T::AccountId, Claim),
+        /// [owner, claim, signer]
+        SignedProofCreated(T::AccountId, Claim, T::AccountId),
+        /// [owner, claim, size]
+        DocumentStored(T::AccountId, Claim, u32),
+        /// [owner, root, leaf_count]
+        BatchProofCreated(T::AccountId, Claim, u32),
+        ProofRevoked(T::AccountId, Claim),
+        /// [owner, to, claim]
+        TransferOffered(T::AccountId, T::AccountId, Claim),
+        /// [new_owner, claim]
+        ProofTransferred(T::AccountId, Claim),
+        /// [owner, claim]
+        TransferCancelled(T::AccountId, Claim),
+        MetadataUpdated(T::AccountId, Claim),
+        /// [owner, claim, expires_at]
+        ExpiryExtended(T::AccountId, Claim, Option<T::BlockNumber>),
+        /// [owner, claim]
+        ProofExpired(T::AccountId, Claim),
+        /// [attester, claim]
+        Attested(T::AccountId, Claim),
+        /// [attester, claim]
+        AttestationRevoked(T::AccountId, Claim),
+        /// [owner, claim]
+        RequiredAttestersSet(T::AccountId, Claim),
+        /// Every required attester has signed. [claim]
+        ProofCompleted(Claim),
+        /// [owner, namespace]
+        NamespaceRegistered(T::AccountId, NamespaceId),
+        /// [owner, namespace]
+        NamespaceMembersSet(T::AccountId, NamespaceId),
     }
 
     #[pallet::error]
@@ -46,62 +511,720 @@ pub mod pallet {
         ProofAlreadyExist,
         ProofNotExist,
         NotProofOwner,
-        ProofExceedsLengthLimit,
+        InvalidExpiry,
+        TooManyExpiring,
+        EmptyBatch,
+        AlreadyAttested,
+        NotAttested,
+        TooManyAttesters,
+        NotRequiredAttester,
+        BadSignature,
+        BadNonce,
+        NamespaceNotExist,
+        NamespaceIdOverflow,
+        NotNamespaceOwner,
+        NotNamespaceMember,
+        ClaimRevoked,
+        NoPendingTransfer,
+        NotTransferRecipient,
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// Removes the proofs expiring at `now`. `schedule_expiry` lets at most
+        /// `MaxExpiringPerBlock` of them expire in one block, so there is nothing left over for
+        /// later blocks.
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let db = T::DbWeight::get();
+            let mut weight = db.reads_writes(1, 1);
+
+            for claim in Expiring::<T>::take(now).into_inner() {
+                weight = weight.saturating_add(db.reads(1));
+                if let Some(info) = Proofs::<T>::get(&claim) {
+                    weight = weight.saturating_add(Self::remove_proof(&claim, &info));
+                    Self::deposit_event(Event::ProofExpired(info.owner, claim));
+                    weight = weight.saturating_add(db.writes(1));
+                }
+            }
+
+            weight
+        }
+
+        fn on_runtime_upgrade() -> Weight {
+            crate::migrations::migrate::<T>()
+        }
+    }
 
     #[pallet::call]
     impl<T: Config> Pallet<T> {
+        /// Registers a proof. With `expires_at` it is removed, and its deposit refunded, at the
+        /// start of that block.
+        #[pallet::weight(0)]
+        #[transactional]
+        pub fn create_proof(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+            metadata: Option<ProofMetadataOf<T>>,
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let claim = Claim { namespace, algorithm, digest };
+            Self::do_create_proof(&sender, &sender, &claim, metadata, expires_at)?;
+
+            Self::deposit_event(Event::ProofCreated(sender, claim));
+
+            Ok(().into())
+        }
+
+        /// Registers a proof owned by `signer`, who authorised it off-chain by signing
+        /// [`Pallet::create_proof_payload`]. The caller relays the transaction and pays the fee
+        /// and the storage deposit.
         #[pallet::weight(0)]
-        pub fn create_proof(origin: OriginFor<T>, proof: Vec<u8>) -> DispatchResultWithPostInfo {
-            if proof.len() > T::ProofLimit::get() as usize {
-                return Err(Error::<T>::ProofExceedsLengthLimit.into())
+        #[transactional]
+        pub fn create_proof_for(
+            origin: OriginFor<T>,
+            signer: T::AccountId,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+            signature: T::OffchainSignature,
+            nonce: u64,
+        ) -> DispatchResultWithPostInfo {
+            let relayer = ensure_signed(origin)?;
+            ensure!(nonce == Self::signer_nonce(&signer), Error::<T>::BadNonce);
+
+            let claim = Claim { namespace, algorithm, digest };
+            let payload = Self::create_proof_payload(&signer, &claim, nonce);
+            ensure!(signature.verify(&payload[..], &signer), Error::<T>::BadSignature);
+
+            SignerNonces::<T>::insert(&signer, nonce.saturating_add(1));
+            Self::do_create_proof(&signer, &relayer, &claim, None, None)?;
+
+            Self::deposit_event(Event::ProofCreated(signer, claim));
+
+            Ok(().into())
+        }
+
+        /// Registers a proof of a document `signer` signed. The signature covers
+        /// [`Pallet::signed_proof_payload`] for the caller, who owns the proof, and is checked
+        /// here; the signer's account is kept with the proof.
+        #[pallet::weight(0)]
+        #[transactional]
+        pub fn create_signed_proof(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+            signer: T::SigningPublicKey,
+            signature: T::OffchainSignature,
+            metadata: Option<ProofMetadataOf<T>>,
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let signer = signer.into_account();
+            let claim = Claim { namespace, algorithm, digest };
+            let payload = Self::signed_proof_payload(&sender, &claim);
+            ensure!(signature.verify(&payload[..], &signer), Error::<T>::BadSignature);
+
+            Self::do_create_proof(&sender, &sender, &claim, metadata, expires_at)?;
+            Signers::<T>::insert(&claim, &signer);
+
+            Self::deposit_event(Event::SignedProofCreated(sender, claim, signer));
+
+            Ok(().into())
+        }
+
+        /// Registers a proof of `data` and writes the document itself to offchain storage through
+        /// offchain indexing. Only its digest and size are kept on chain. Charges
+        /// `DocumentFeePerByte` for every byte.
+        #[pallet::weight(Pallet::<T>::store_document_weight(data.len() as u32))]
+        #[transactional]
+        pub fn store_document(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            data: BoundedVec<u8, T::MaxDocumentSize>,
+            metadata: Option<ProofMetadataOf<T>>,
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+            let claim = Claim { namespace, algorithm, digest: algorithm.hash(&data) };
+            Self::do_create_proof(&sender, &sender, &claim, metadata, expires_at)?;
+
+            let size = data.len() as u32;
+            let fee = T::DocumentFeePerByte::get().saturating_mul(size.into());
+            let imbalance = T::Currency::withdraw(
+                &sender,
+                fee,
+                WithdrawReasons::FEE,
+                ExistenceRequirement::KeepAlive,
+            )?;
+            T::DocumentFee::on_unbalanced(imbalance);
+
+            for (index, chunk) in data.chunks(DOCUMENT_CHUNK_SIZE).enumerate() {
+                sp_io::offchain_index::set(&Self::document_chunk_key(&claim, index as u32), chunk);
             }
+            Documents::<T>::insert(&claim, size);
 
-            ensure!(!Proofs::<T>::contains_key(&proof), Error::<T>::ProofAlreadyExist);
+            Self::deposit_event(Event::DocumentStored(sender, claim, size));
 
+            Ok(().into())
+        }
+
+        /// Registers the root of the batch tree over `leaf_count` document digests as a single
+        /// proof. The tree is built as [`HashAlgorithm::merkle_root`] describes.
+        #[pallet::weight(0)]
+        #[transactional]
+        pub fn create_batch_proof(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            root: [u8; 32],
+            leaf_count: u32,
+            metadata: Option<ProofMetadataOf<T>>,
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
             let sender = ensure_signed(origin)?;
-            Proofs::<T>::insert(
-                &proof,
-                (sender.clone(), frame_system::Pallet::<T>::block_number()),
-            );
-            Self::deposit_event(Event::ProofCreated(sender, proof));
+            ensure!(leaf_count > 0, Error::<T>::EmptyBatch);
+
+            let claim = Claim { namespace, algorithm, digest: root };
+            Self::do_create_proof(&sender, &sender, &claim, metadata, expires_at)?;
+            Batches::<T>::insert(&claim, leaf_count);
+
+            Self::deposit_event(Event::BatchProofCreated(sender, claim, leaf_count));
+
+            Ok(().into())
+        }
+
+        /// Removes a proof and refunds its deposit. A tombstone keeps who revoked it, when and why,
+        /// and the caller reserves [`Pallet::tombstone_deposit`] for it.
+        #[pallet::weight(0)]
+        #[transactional]
+        pub fn revoke_proof(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+            reason: RevocationReasonOf<T>,
+        ) -> DispatchResultWithPostInfo {
+            let claim = Claim { namespace, algorithm, digest };
+            let sender = ensure_signed(origin)?;
+            let info = Proofs::<T>::get(&claim)
+                .ok_or(Error::<T>::ProofNotExist)?;
+
+            ensure!(info.owner == sender, Error::<T>::NotProofOwner);
+
+            if let Some(expires_at) = Expiry::<T>::get(&claim) {
+                Self::unschedule_expiry(&claim, expires_at);
+            }
+            Self::remove_proof(&claim, &info);
+            let deposit = Self::tombstone_deposit(&claim);
+            T::Currency::reserve(&sender, deposit)?;
+            Tombstones::<T>::insert(&claim, Tombstone {
+                revoked_by: sender.clone(),
+                block_number: frame_system::Pallet::<T>::block_number(),
+                reason,
+                deposit,
+            });
+            Self::deposit_event(Event::ProofRevoked(sender, claim));
+
+            Ok(().into())
+        }
+
+        /// Offers a proof to `to`, who takes it over with `accept_proof_transfer`. A new offer
+        /// replaces the pending one.
+        #[pallet::weight(0)]
+        pub fn offer_proof_transfer(
+            origin: OriginFor<T>,
+            to: T::AccountId,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+        ) -> DispatchResultWithPostInfo {
+            let claim = Claim { namespace, algorithm, digest };
+            let sender = ensure_signed(origin)?;
+            let info = Proofs::<T>::get(&claim)
+                .ok_or(Error::<T>::ProofNotExist)?;
+
+            ensure!(info.owner == sender, Error::<T>::NotProofOwner);
+            Self::ensure_member(claim.namespace, &to)?;
+
+            PendingTransfers::<T>::insert(&claim, &to);
+            Self::deposit_event(Event::TransferOffered(sender, to, claim));
+
+            Ok(().into())
+        }
+
+        /// Takes over a proof offered to the caller, along with its deposit if
+        /// `DepositMovesWithProof` is set.
+        #[pallet::weight(0)]
+        pub fn accept_proof_transfer(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+        ) -> DispatchResultWithPostInfo {
+            let claim = Claim { namespace, algorithm, digest };
+            let sender = ensure_signed(origin)?;
+            let to = PendingTransfers::<T>::get(&claim)
+                .ok_or(Error::<T>::NoPendingTransfer)?;
+            ensure!(to == sender, Error::<T>::NotTransferRecipient);
+            let mut info = Proofs::<T>::get(&claim)
+                .ok_or(Error::<T>::ProofNotExist)?;
+            // Membership may have changed since the offer.
+            Self::ensure_member(claim.namespace, &to)?;
+
+            if T::DepositMovesWithProof::get() {
+                T::Currency::reserve(&to, info.deposit)?;
+                T::Currency::unreserve(&info.depositor, info.deposit);
+                info.depositor = to.clone();
+            }
+            let from = sp_std::mem::replace(&mut info.owner, to.clone());
+
+            PendingTransfers::<T>::remove(&claim);
+            Proofs::<T>::insert(&claim, info);
+            ProofsByOwner::<T>::remove(&from, &claim);
+            ProofsByOwner::<T>::insert(&to, &claim, ());
+            Self::record_transfer(&claim, from, to.clone());
+            Self::deposit_event(Event::ProofTransferred(to, claim));
 
             Ok(().into())
         }
 
+        /// Withdraws the pending offer of a proof.
         #[pallet::weight(0)]
-        pub fn revoke_proof(origin: OriginFor<T>, proof: Vec<u8>) -> DispatchResultWithPostInfo {
+        pub fn cancel_proof_transfer(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+        ) -> DispatchResultWithPostInfo {
+            let claim = Claim { namespace, algorithm, digest };
             let sender = ensure_signed(origin)?;
-            let (owner, _) = Proofs::<T>::get(&proof)
+            let info = Proofs::<T>::get(&claim)
                 .ok_or(Error::<T>::ProofNotExist)?;
 
-            ensure!(owner == sender, Error::<T>::NotProofOwner);
+            ensure!(info.owner == sender, Error::<T>::NotProofOwner);
+            ensure!(PendingTransfers::<T>::contains_key(&claim), Error::<T>::NoPendingTransfer);
 
-            Proofs::<T>::remove(&proof);
-            Self::deposit_event(Event::ProofRevoked(sender, proof));
+            PendingTransfers::<T>::remove(&claim);
+            Self::deposit_event(Event::TransferCancelled(sender, claim));
 
             Ok(().into())
         }
 
+        /// Replaces or, with `None`, clears the metadata of a proof. The owner pays the deposit
+        /// for the resized proof and the previous depositor is refunded.
         #[pallet::weight(0)]
-        pub fn transfer_proof(origin: OriginFor<T>, to: T::AccountId, proof: Vec<u8>)
-                              -> DispatchResultWithPostInfo {
+        pub fn set_metadata(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+            metadata: Option<ProofMetadataOf<T>>,
+        ) -> DispatchResultWithPostInfo {
+            let claim = Claim { namespace, algorithm, digest };
             let sender = ensure_signed(origin)?;
-            let (owner, _) = Proofs::<T>::get(&proof)
+            let mut info = Proofs::<T>::get(&claim)
                 .ok_or(Error::<T>::ProofNotExist)?;
 
-            ensure!(owner == sender, Error::<T>::NotProofOwner);
+            ensure!(info.owner == sender, Error::<T>::NotProofOwner);
+
+            let deposit = Self::deposit_for(&claim, &info, metadata.as_ref());
+            T::Currency::reserve(&sender, deposit)?;
+            T::Currency::unreserve(&info.depositor, info.deposit);
+            info.depositor = sender.clone();
+            info.deposit = deposit;
+
+            Proofs::<T>::insert(&claim, info);
+            Metadata::<T>::set(&claim, metadata);
+            Self::deposit_event(Event::MetadataUpdated(sender, claim));
+
+            Ok(().into())
+        }
+
+        /// Moves the expiry of a proof to a later block, or drops it with `None`.
+        #[pallet::weight(0)]
+        #[transactional]
+        pub fn extend_expiry(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            let claim = Claim { namespace, algorithm, digest };
+            let sender = ensure_signed(origin)?;
+            let info = Proofs::<T>::get(&claim)
+                .ok_or(Error::<T>::ProofNotExist)?;
+
+            ensure!(info.owner == sender, Error::<T>::NotProofOwner);
+
+            let current = Expiry::<T>::get(&claim).ok_or(Error::<T>::InvalidExpiry)?;
+            Self::unschedule_expiry(&claim, current);
+            if let Some(expires_at) = expires_at {
+                ensure!(expires_at > current, Error::<T>::InvalidExpiry);
+                Self::schedule_expiry(&claim, expires_at)?;
+            }
+
+            Self::deposit_event(Event::ExpiryExtended(sender, claim, expires_at));
 
-            Proofs::<T>::insert(
-                &proof,
-                (to.clone(), frame_system::Pallet::<T>::block_number()),
+            Ok(().into())
+        }
+
+        /// Endorses a proof. Only the accounts its owner requires can attest, which keeps the
+        /// list bounded by `RequiredAttesters`.
+        #[pallet::weight(0)]
+        pub fn attest(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+        ) -> DispatchResultWithPostInfo {
+            let claim = Claim { namespace, algorithm, digest };
+            let sender = ensure_signed(origin)?;
+            ensure!(Proofs::<T>::contains_key(&claim), Error::<T>::ProofNotExist);
+            ensure!(
+                Self::required_attesters(&claim).contains(&sender),
+                Error::<T>::NotRequiredAttester
             );
-            Self::deposit_event(Event::ProofTransferred(to, proof));
+
+            Attestations::<T>::try_mutate(&claim, |attestations| -> DispatchResult {
+                ensure!(
+                    !attestations.iter().any(|(attester, _)| *attester == sender),
+                    Error::<T>::AlreadyAttested
+                );
+                attestations
+                    .try_push((sender.clone(), frame_system::Pallet::<T>::block_number()))
+                    .map_err(|_| Error::<T>::TooManyAttesters)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::Attested(sender.clone(), claim));
+            if Self::is_complete(&claim) {
+                Self::deposit_event(Event::ProofCompleted(claim));
+            }
+
+            Ok(().into())
+        }
+
+        #[pallet::weight(0)]
+        pub fn revoke_attestation(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+        ) -> DispatchResultWithPostInfo {
+            let claim = Claim { namespace, algorithm, digest };
+            let sender = ensure_signed(origin)?;
+
+            Attestations::<T>::try_mutate(&claim, |attestations| -> DispatchResult {
+                let index = attestations.iter().position(|(attester, _)| *attester == sender)
+                    .ok_or(Error::<T>::NotAttested)?;
+                attestations.remove(index);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::AttestationRevoked(sender, claim));
+
+            Ok(().into())
+        }
+
+        /// Names the accounts that must attest before the proof counts as complete. Attestations
+        /// by accounts no longer named are dropped.
+        #[pallet::weight(0)]
+        pub fn set_required_attesters(
+            origin: OriginFor<T>,
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            digest: [u8; 32],
+            attesters: BoundedVec<T::AccountId, T::MaxAttesters>,
+        ) -> DispatchResultWithPostInfo {
+            let claim = Claim { namespace, algorithm, digest };
+            let sender = ensure_signed(origin)?;
+            let info = Proofs::<T>::get(&claim)
+                .ok_or(Error::<T>::ProofNotExist)?;
+
+            ensure!(info.owner == sender, Error::<T>::NotProofOwner);
+
+            Attestations::<T>::mutate_exists(&claim, |attestations| {
+                if let Some(list) = attestations {
+                    list.retain(|(attester, _)| attesters.contains(attester));
+                    if list.is_empty() {
+                        *attestations = None;
+                    }
+                }
+            });
+            RequiredAttesters::<T>::insert(&claim, attesters);
+
+            Self::deposit_event(Event::RequiredAttestersSet(sender, claim));
+
+            Ok(().into())
+        }
+
+        /// Registers a namespace owned by the caller, who reserves a deposit for it.
+        #[pallet::weight(0)]
+        pub fn register_namespace(
+            origin: OriginFor<T>,
+            members: BoundedVec<T::AccountId, T::MaxNamespaceMembers>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            let namespace = Self::next_namespace_id();
+            let next = namespace.checked_add(1).ok_or(Error::<T>::NamespaceIdOverflow)?;
+            let deposit = Self::namespace_deposit();
+            T::Currency::reserve(&sender, deposit)?;
+
+            Namespaces::<T>::insert(namespace, NamespaceInfo { owner: sender.clone(), members, deposit });
+            NextNamespaceId::<T>::put(next);
+
+            Self::deposit_event(Event::NamespaceRegistered(sender, namespace));
+
+            Ok(().into())
+        }
+
+        /// Replaces the members of a namespace. Proofs owned by removed members stay in place.
+        #[pallet::weight(0)]
+        pub fn set_namespace_members(
+            origin: OriginFor<T>,
+            namespace: NamespaceId,
+            members: BoundedVec<T::AccountId, T::MaxNamespaceMembers>,
+        ) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            Namespaces::<T>::try_mutate(namespace, |info| -> DispatchResult {
+                let info = info.as_mut().ok_or(Error::<T>::NamespaceNotExist)?;
+                ensure!(info.owner == sender, Error::<T>::NotNamespaceOwner);
+                info.members = members;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::NamespaceMembersSet(sender, namespace));
 
             Ok(().into())
         }
     }
-}
\ No newline at end of file
+
+    impl<T: Config> Pallet<T> {
+        /// The deposit for the bytes `claim`, `info` and `metadata` take up in storage.
+        pub fn deposit_for(claim: &Claim, info: &ProofInfoOf<T>, metadata: Option<&ProofMetadataOf<T>>)
+                           -> BalanceOf<T> {
+            let metadata_bytes = metadata.map_or(0, |metadata| claim.encoded_size() + metadata.encoded_size());
+            let bytes = (claim.encoded_size() + info.encoded_size() + metadata_bytes) as u32;
+            T::DepositPerByte::get().saturating_mul(bytes.into())
+        }
+
+        /// Tombstones reserve for the longest reason they can hold, since they are kept for good
+        /// unless the claim may be registered again.
+        pub fn tombstone_deposit(claim: &Claim) -> BalanceOf<T> {
+            let bytes = (claim.encoded_size() + TombstoneOf::<T>::max_encoded_len()) as u32;
+            T::DepositPerByte::get().saturating_mul(bytes.into())
+        }
+
+        /// Namespaces reserve for the largest member list they can hold, so changing the
+        /// members never has to adjust the deposit.
+        pub fn namespace_deposit() -> BalanceOf<T> {
+            let bytes = (NamespaceId::max_encoded_len() + NamespaceInfoOf::<T>::max_encoded_len()) as u32;
+            T::DepositPerByte::get().saturating_mul(bytes.into())
+        }
+
+        fn ensure_member(namespace: Option<NamespaceId>, who: &T::AccountId) -> DispatchResult {
+            if let Some(namespace) = namespace {
+                let info = Self::namespaces(namespace).ok_or(Error::<T>::NamespaceNotExist)?;
+                ensure!(
+                    info.owner == *who || info.members.contains(who),
+                    Error::<T>::NotNamespaceMember
+                );
+            }
+            Ok(())
+        }
+
+        /// The bytes `signer` signs to authorise `create_proof_for`. The genesis hash keeps the
+        /// signature from being replayed on another chain.
+        pub fn create_proof_payload(signer: &T::AccountId, claim: &Claim, nonce: u64) -> Vec<u8> {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+            (b"poe/create_proof_for", genesis_hash, signer, claim, nonce).encode()
+        }
+
+        /// The bytes a signer signs for `create_signed_proof` submitted by `owner`. Covering the
+        /// owner keeps anyone who copies the signature from the pool from registering the proof
+        /// as their own. Covering the whole claim and the genesis hash keeps it from being reused
+        /// under another namespace or algorithm, or on another chain.
+        pub fn signed_proof_payload(owner: &T::AccountId, claim: &Claim) -> Vec<u8> {
+            let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+            (b"poe/signed", genesis_hash, owner, claim).encode()
+        }
+
+        fn do_create_proof(
+            owner: &T::AccountId,
+            depositor: &T::AccountId,
+            claim: &Claim,
+            metadata: Option<ProofMetadataOf<T>>,
+            expires_at: Option<T::BlockNumber>,
+        ) -> DispatchResult {
+            ensure!(!Proofs::<T>::contains_key(claim), Error::<T>::ProofAlreadyExist);
+            if let Some(tombstone) = Tombstones::<T>::get(claim) {
+                ensure!(T::AllowReregistration::get(), Error::<T>::ClaimRevoked);
+                T::Currency::unreserve(&tombstone.revoked_by, tombstone.deposit);
+                Tombstones::<T>::remove(claim);
+            }
+            Self::ensure_member(claim.namespace, owner)?;
+            if let Some(expires_at) = expires_at {
+                Self::schedule_expiry(claim, expires_at)?;
+            }
+
+            let mut info = ProofInfo {
+                owner: owner.clone(),
+                block_number: frame_system::Pallet::<T>::block_number(),
+                created_at: Some(T::Time::now()),
+                depositor: depositor.clone(),
+                deposit: Default::default(),
+            };
+            info.deposit = Self::deposit_for(claim, &info, metadata.as_ref());
+            T::Currency::reserve(depositor, info.deposit)?;
+
+            Proofs::<T>::insert(claim, info);
+            ProofsByOwner::<T>::insert(owner, claim, ());
+            ProofCount::<T>::mutate(|count| *count = count.saturating_add(1));
+            if let Some(metadata) = metadata {
+                Metadata::<T>::insert(claim, metadata);
+            }
+
+            Ok(())
+        }
+
+        /// The proof of the batch rooted at `root` if the document digest `leaf` is part of it.
+        /// `path` holds the sibling of every node from the leaf up to the root, and must be
+        /// exactly [`batch_depth`] long for the size of the batch.
+        pub fn verify_inclusion(
+            namespace: Option<NamespaceId>,
+            algorithm: HashAlgorithm,
+            root: [u8; 32],
+            leaf: [u8; 32],
+            path: Vec<[u8; 32]>,
+        ) -> Option<ProofInfoOf<T>> {
+            let claim = Claim { namespace, algorithm, digest: root };
+            let leaf_count = Batches::<T>::get(&claim)?;
+            if path.len() != batch_depth(leaf_count) as usize {
+                return None;
+            }
+
+            let computed = path
+                .iter()
+                .fold(algorithm.hash_leaf(&leaf), |node, sibling| algorithm.hash_pair(&node, sibling));
+            if computed != root {
+                return None;
+            }
+
+            Proofs::<T>::get(&claim)
+        }
+
+        /// Clears everything stored for `claim`, refunds its deposit and returns the weight of
+        /// doing so. Any expiry must already be unscheduled.
+        fn remove_proof(claim: &Claim, info: &ProofInfoOf<T>) -> Weight {
+            let mut chunks = 0;
+            T::Currency::unreserve(&info.depositor, info.deposit);
+            Proofs::<T>::remove(claim);
+            ProofsByOwner::<T>::remove(&info.owner, claim);
+            ProofCount::<T>::mutate(|count| *count = count.saturating_sub(1));
+            Metadata::<T>::remove(claim);
+            ProofHistory::<T>::remove(claim);
+            PendingTransfers::<T>::remove(claim);
+            Signers::<T>::remove(claim);
+            if let Some(size) = Documents::<T>::take(claim) {
+                chunks = ((size as usize + DOCUMENT_CHUNK_SIZE - 1) / DOCUMENT_CHUNK_SIZE) as u32;
+                for index in 0..chunks {
+                    sp_io::offchain_index::clear(&Self::document_chunk_key(claim, index));
+                }
+            }
+            Expiry::<T>::remove(claim);
+            Batches::<T>::remove(claim);
+            Attestations::<T>::remove(claim);
+            RequiredAttesters::<T>::remove(claim);
+
+            // Reads the depositor's account, `ProofCount` and `Documents`. Writes those, the
+            // ten other items above and every cleared document chunk.
+            T::DbWeight::get().reads_writes(3, 13u64.saturating_add(chunks as Weight))
+        }
+
+        /// Weight of `store_document` for a document of `size` bytes.
+        fn store_document_weight(size: u32) -> Weight {
+            let chunks = (size as usize + DOCUMENT_CHUNK_SIZE - 1) / DOCUMENT_CHUNK_SIZE;
+            // Registering the proof reads `Proofs`, `Tombstones`, `Namespaces`, `Expiring`,
+            // `ProofCount` and the sender's account, and writes all but `Proofs` and `Namespaces`
+            // along with `Proofs`, `ProofsByOwner`, `Expiry` and `Metadata`. The fee writes the
+            // account again, then come `Documents` and every indexed chunk.
+            T::DbWeight::get()
+                .reads_writes(6, 10u64.saturating_add(chunks as Weight))
+                .saturating_add(DOCUMENT_WEIGHT_PER_BYTE.saturating_mul(size as Weight))
+        }
+
+        /// The `page`th batch of [`PROOFS_PAGE_SIZE`] proofs owned by `owner`. Pages follow the
+        /// storage order of the index, which only stays stable while the owner's proofs do.
+        pub fn proofs_of(owner: T::AccountId, page: u32) -> Vec<(Claim, ProofInfoOf<T>)> {
+            ProofsByOwner::<T>::iter_prefix(owner)
+                .skip((page as usize).saturating_mul(PROOFS_PAGE_SIZE))
+                .take(PROOFS_PAGE_SIZE)
+                .filter_map(|(claim, ())| Proofs::<T>::get(&claim).map(|info| (claim, info)))
+                .collect()
+        }
+
+        /// The offchain storage key of the `index`th chunk of the document proven by `claim`.
+        pub fn document_chunk_key(claim: &Claim, index: u32) -> Vec<u8> {
+            (b"poe/document", claim, index).encode()
+        }
+
+        /// Whether `signature` signs [`Pallet::signed_proof_payload`] of `claim` for `owner`, the
+        /// account that created the proof, with the signer stored with its proof. Proofs that
+        /// were not created signed never match.
+        pub fn check_signature(
+            claim: &Claim,
+            owner: &T::AccountId,
+            signature: &T::OffchainSignature,
+        ) -> bool {
+            let payload = Self::signed_proof_payload(owner, claim);
+            Self::signers(claim).map_or(false, |signer| signature.verify(&payload[..], &signer))
+        }
+
+        /// Whether every required attester has attested `claim`.
+        pub fn is_complete(claim: &Claim) -> bool {
+            let attestations = Self::attestations(claim);
+            Self::required_attesters(claim)
+                .iter()
+                .all(|required| attestations.iter().any(|(attester, _)| attester == required))
+        }
+
+        fn schedule_expiry(claim: &Claim, expires_at: T::BlockNumber) -> DispatchResult {
+            ensure!(expires_at > frame_system::Pallet::<T>::block_number(), Error::<T>::InvalidExpiry);
+
+            Expiring::<T>::try_mutate(expires_at, |expiring| {
+                expiring.try_push(*claim).map_err(|_| Error::<T>::TooManyExpiring)
+            })?;
+            Expiry::<T>::insert(claim, expires_at);
+
+            Ok(())
+        }
+
+        fn unschedule_expiry(claim: &Claim, expires_at: T::BlockNumber) {
+            Expiring::<T>::mutate(expires_at, |expiring| expiring.retain(|c| c != claim));
+            Expiry::<T>::remove(claim);
+        }
+
+        fn record_transfer(claim: &Claim, from: T::AccountId, to: T::AccountId) {
+            let record = TransferRecord {
+                from,
+                to,
+                block_number: frame_system::Pallet::<T>::block_number(),
+            };
+
+            ProofHistory::<T>::mutate(claim, |history| {
+                if !history.is_empty() && history.len() >= T::MaxHistoryLength::get() as usize {
+                    history.remove(0);
+                }
+                let _ = history.try_push(record);
+            });
+        }
+    }
+}
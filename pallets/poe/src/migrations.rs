@@ -0,0 +1,347 @@
+//! Storage migrations for the proof of existence pallet.
+
+use crate::*;
+use frame_support::{
+    traits::{Get, GetStorageVersion, PalletInfoAccess, StorageVersion},
+    weights::Weight,
+};
+
+/// Runs every migration the on-chain storage version has not seen yet.
+pub fn migrate<T: Config>() -> Weight {
+    let mut weight: Weight = 0;
+
+    if Pallet::<T>::on_chain_storage_version() < 1 {
+        weight = weight.saturating_add(v1::migrate::<T>());
+        StorageVersion::new(1).put::<Pallet<T>>();
+    }
+
+    if Pallet::<T>::on_chain_storage_version() < 2 {
+        weight = weight.saturating_add(v2::migrate::<T>());
+        StorageVersion::new(2).put::<Pallet<T>>();
+    }
+
+    if Pallet::<T>::on_chain_storage_version() < 3 {
+        weight = weight.saturating_add(v3::migrate::<T>());
+        StorageVersion::new(3).put::<Pallet<T>>();
+    }
+
+    if Pallet::<T>::on_chain_storage_version() < 4 {
+        weight = weight.saturating_add(v4::migrate::<T>());
+        StorageVersion::new(4).put::<Pallet<T>>();
+    }
+
+    if Pallet::<T>::on_chain_storage_version() < 5 {
+        weight = weight.saturating_add(v5::migrate::<T>());
+        StorageVersion::new(5).put::<Pallet<T>>();
+    }
+
+    if Pallet::<T>::on_chain_storage_version() < 6 {
+        weight = weight.saturating_add(v6::migrate::<T>());
+        StorageVersion::new(6).put::<Pallet<T>>();
+    }
+
+    if Pallet::<T>::on_chain_storage_version() < 7 {
+        weight = weight.saturating_add(v7::migrate::<T>());
+        StorageVersion::new(7).put::<Pallet<T>>();
+    }
+
+    weight.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+}
+
+/// A [`Claim`] as encoded before namespaces existed. Versions 1 to 4 key storage by it.
+pub type OldClaim = (HashAlgorithm, [u8; 32]);
+
+/// Version 1 keys proofs by a typed [`Claim`] instead of the raw claim bytes.
+pub mod v1 {
+    use super::*;
+    use codec::Encode;
+    use frame_support::{storage::migration::storage_key_iter, Blake2_128Concat, StorageHasher};
+    use sp_std::{convert::TryInto, vec::Vec};
+
+    /// Re-keys every raw claim. A 32-byte claim is taken to be the Blake2-256 digest clients
+    /// submitted for their document and is kept as is, so it can still be looked up by that
+    /// digest. Any other claim is keyed by its own Blake2-256 digest.
+    pub fn migrate<T: Config>() -> Weight {
+        let pallet_name = <Pallet<T> as PalletInfoAccess>::name();
+        let old_proofs: Vec<_> = storage_key_iter::<
+            Vec<u8>,
+            (T::AccountId, T::BlockNumber),
+            Blake2_128Concat,
+        >(pallet_name.as_bytes(), b"Proofs")
+            .drain()
+            .collect();
+
+        let count = old_proofs.len() as Weight;
+        for (raw, value) in old_proofs {
+            let digest = match raw.as_slice().try_into() {
+                Ok(digest) => digest,
+                Err(_) => HashAlgorithm::Blake2_256.hash(&raw),
+            };
+            let claim: OldClaim = (HashAlgorithm::Blake2_256, digest);
+            frame_support::storage::migration::put_storage_value(
+                pallet_name.as_bytes(),
+                b"Proofs",
+                &Blake2_128Concat::hash(&claim.encode()),
+                value,
+            );
+        }
+
+        T::DbWeight::get().reads_writes(count, count.saturating_mul(2))
+    }
+}
+
+/// Version 2 stores the storage deposit next to the owner.
+pub mod v2 {
+    use super::*;
+    use codec::{Decode, Encode};
+    use frame_support::{
+        storage::migration::{put_storage_value, storage_key_iter},
+        Blake2_128Concat, StorageHasher,
+    };
+    use sp_std::vec::Vec;
+
+    #[derive(Encode, Decode)]
+    pub struct OldProofInfo<AccountId, BlockNumber, Balance> {
+        pub owner: AccountId,
+        pub block_number: BlockNumber,
+        pub depositor: AccountId,
+        pub deposit: Balance,
+    }
+
+    /// Proofs created before deposits existed keep a zero deposit.
+    pub fn migrate<T: Config>() -> Weight {
+        let pallet_name = <Pallet<T> as PalletInfoAccess>::name();
+        let old_proofs: Vec<_> = storage_key_iter::<
+            OldClaim,
+            (T::AccountId, T::BlockNumber),
+            Blake2_128Concat,
+        >(pallet_name.as_bytes(), b"Proofs")
+            .collect();
+
+        let count = old_proofs.len() as Weight;
+        for (claim, (owner, block_number)) in old_proofs {
+            let info = OldProofInfo::<_, _, BalanceOf<T>> {
+                depositor: owner.clone(),
+                owner,
+                block_number,
+                deposit: Default::default(),
+            };
+            put_storage_value(
+                pallet_name.as_bytes(),
+                b"Proofs",
+                &Blake2_128Concat::hash(&claim.encode()),
+                info,
+            );
+        }
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+}
+
+/// Version 3 keeps the creation timestamp; transfers no longer move `block_number`.
+pub mod v3 {
+    use super::*;
+    use codec::{Decode, Encode};
+    use frame_support::{
+        storage::migration::{put_storage_value, storage_key_iter},
+        Blake2_128Concat, StorageHasher,
+    };
+    use sp_std::vec::Vec;
+    use v2::OldProofInfo;
+
+    /// [`ProofInfo`] from versions 3 to 6, with a creation time that is always set.
+    #[derive(Encode, Decode)]
+    pub struct V3ProofInfo<AccountId, BlockNumber, Balance, Moment> {
+        pub owner: AccountId,
+        pub block_number: BlockNumber,
+        pub created_at: Moment,
+        pub depositor: AccountId,
+        pub deposit: Balance,
+    }
+
+    pub type V3ProofInfoOf<T> = V3ProofInfo<
+        <T as frame_system::Config>::AccountId,
+        <T as frame_system::Config>::BlockNumber,
+        BalanceOf<T>,
+        MomentOf<T>,
+    >;
+
+    /// The real creation time of existing proofs is unknown, so it is left at zero. [`v7`]
+    /// turns that into `None`.
+    pub fn migrate<T: Config>() -> Weight {
+        let pallet_name = <Pallet<T> as PalletInfoAccess>::name();
+        let old_proofs: Vec<_> = storage_key_iter::<
+            OldClaim,
+            OldProofInfo<T::AccountId, T::BlockNumber, BalanceOf<T>>,
+            Blake2_128Concat,
+        >(pallet_name.as_bytes(), b"Proofs")
+            .collect();
+
+        let count = old_proofs.len() as Weight;
+        for (claim, old) in old_proofs {
+            let info: V3ProofInfoOf<T> = V3ProofInfo {
+                owner: old.owner,
+                block_number: old.block_number,
+                created_at: Default::default(),
+                depositor: old.depositor,
+                deposit: old.deposit,
+            };
+            put_storage_value(
+                pallet_name.as_bytes(),
+                b"Proofs",
+                &Blake2_128Concat::hash(&claim.encode()),
+                info,
+            );
+        }
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+}
+
+/// Version 4 indexes proofs by owner and counts them.
+pub mod v4 {
+    use super::*;
+    use frame_support::{storage::migration::storage_key_iter, Blake2_128Concat};
+
+    /// Only counts; the owner index is keyed by [`Claim`] and so is built by [`v5`].
+    pub fn migrate<T: Config>() -> Weight {
+        let pallet_name = <Pallet<T> as PalletInfoAccess>::name();
+        let count = storage_key_iter::<OldClaim, v3::V3ProofInfoOf<T>, Blake2_128Concat>(
+            pallet_name.as_bytes(),
+            b"Proofs",
+        )
+            .count() as u32;
+        ProofCount::<T>::put(count);
+
+        T::DbWeight::get().reads_writes(count.into(), 1)
+    }
+}
+
+/// Version 5 adds the namespace to [`Claim`]. Existing claims move to the global namespace.
+pub mod v5 {
+    use super::*;
+    use codec::{Decode, Encode};
+    use frame_support::{
+        storage::{
+            bounded_vec::BoundedVec,
+            migration::{put_storage_value, remove_storage_prefix, storage_key_iter},
+        },
+        Blake2_128Concat, StorageHasher,
+    };
+    use sp_std::{convert::TryInto, vec::Vec};
+
+    fn global((algorithm, digest): OldClaim) -> Claim {
+        Claim { namespace: None, algorithm, digest }
+    }
+
+    /// Drains the map `item` keyed by [`OldClaim`] and hands every entry to `insert`.
+    fn rekey<T: Config, V: Decode>(item: &[u8], insert: impl Fn(&Claim, V)) -> Weight {
+        let pallet_name = <Pallet<T> as PalletInfoAccess>::name();
+        let entries: Vec<_> =
+            storage_key_iter::<OldClaim, V, Blake2_128Concat>(pallet_name.as_bytes(), item)
+                .drain()
+                .collect();
+
+        let count = entries.len() as Weight;
+        for (claim, value) in entries {
+            insert(&global(claim), value);
+        }
+
+        T::DbWeight::get().reads_writes(count, count.saturating_mul(2))
+    }
+
+    pub fn migrate<T: Config>() -> Weight {
+        let pallet_name = <Pallet<T> as PalletInfoAccess>::name();
+        // Proofs still have the version 3 layout, which `Proofs` no longer decodes.
+        let mut weight = rekey::<T, v3::V3ProofInfoOf<T>>(b"Proofs", |claim, v| {
+            let key = Blake2_128Concat::hash(&claim.encode());
+            put_storage_value(pallet_name.as_bytes(), b"Proofs", &key, v)
+        });
+        weight = weight
+            .saturating_add(rekey::<T, _>(b"Metadata", |claim, v| Metadata::<T>::insert(claim, v)))
+            .saturating_add(rekey::<T, _>(b"ProofHistory", |claim, v| ProofHistory::<T>::insert(claim, v)))
+            .saturating_add(rekey::<T, _>(b"Batches", |claim, v| Batches::<T>::insert(claim, v)))
+            .saturating_add(rekey::<T, _>(b"Expiry", |claim, v| Expiry::<T>::insert(claim, v)))
+            .saturating_add(rekey::<T, _>(b"Attestations", |claim, v| Attestations::<T>::insert(claim, v)))
+            .saturating_add(rekey::<T, _>(b"RequiredAttesters", |claim, v| {
+                RequiredAttesters::<T>::insert(claim, v)
+            }));
+
+        let mut count: Weight = 0;
+        Expiring::<T>::translate::<BoundedVec<OldClaim, T::MaxExpiringPerBlock>, _>(|_, old| {
+            count += 1;
+            let claims: Vec<Claim> = old.into_inner().into_iter().map(global).collect();
+            claims.try_into().ok()
+        });
+        weight = weight.saturating_add(T::DbWeight::get().reads_writes(count, count));
+
+        // The index is rebuilt from scratch, whatever claim encoding it was written with.
+        remove_storage_prefix(pallet_name.as_bytes(), b"ProofsByOwner", &[]);
+        let mut count: Weight = 0;
+        let proofs = storage_key_iter::<Claim, v3::V3ProofInfoOf<T>, Blake2_128Concat>(
+            pallet_name.as_bytes(),
+            b"Proofs",
+        );
+        for (claim, info) in proofs {
+            ProofsByOwner::<T>::insert(&info.owner, &claim, ());
+            count += 1;
+        }
+
+        weight.saturating_add(T::DbWeight::get().reads_writes(count, count.saturating_add(1)))
+    }
+}
+
+/// Version 6 keeps a deposit with every tombstone.
+pub mod v6 {
+    use super::*;
+    use codec::{Decode, Encode};
+
+    #[derive(Encode, Decode)]
+    pub struct OldTombstone<AccountId, BlockNumber, MaxLength: Get<u32>> {
+        pub revoked_by: AccountId,
+        pub block_number: BlockNumber,
+        pub reason: RevocationReason<MaxLength>,
+    }
+
+    /// Tombstones left before deposits existed keep a zero deposit, as proofs did in [`v2`].
+    pub fn migrate<T: Config>() -> Weight {
+        let mut count: Weight = 0;
+        Tombstones::<T>::translate::<OldTombstone<T::AccountId, T::BlockNumber, T::MaxReasonLength>, _>(
+            |_, old| {
+                count += 1;
+                Some(Tombstone {
+                    revoked_by: old.revoked_by,
+                    block_number: old.block_number,
+                    reason: old.reason,
+                    deposit: Default::default(),
+                })
+            },
+        );
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+}
+
+/// Version 7 records an unknown creation time as `None` instead of zero.
+pub mod v7 {
+    use super::*;
+    use sp_runtime::traits::Zero;
+    use v3::V3ProofInfoOf;
+
+    /// The proofs [`v3`] migrated were given a creation time of zero.
+    pub fn migrate<T: Config>() -> Weight {
+        let mut count: Weight = 0;
+        Proofs::<T>::translate::<V3ProofInfoOf<T>, _>(|_, old| {
+            count += 1;
+            Some(ProofInfo {
+                owner: old.owner,
+                block_number: old.block_number,
+                created_at: if old.created_at.is_zero() { None } else { Some(old.created_at) },
+                depositor: old.depositor,
+                deposit: old.deposit,
+            })
+        });
+
+        T::DbWeight::get().reads_writes(count, count)
+    }
+}
@@ -3,12 +3,13 @@ use frame_support::parameter_types;
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
-    testing::Header,
+    testing::{Header, TestSignature, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
+type Balance = u128;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -18,6 +19,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		PoeModule: pallet_poe::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -25,7 +28,22 @@ frame_support::construct_runtime!(
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
-    pub const ProofLimit: u32 = 128;
+    pub const ExistentialDeposit: u128 = 1;
+    pub const MaxLocks: u32 = 50;
+    pub const DepositPerByte: u128 = 1;
+    pub static DepositMovesWithProof: bool = true;
+    pub const MinimumPeriod: u64 = 1;
+    pub const MaxHistoryLength: u32 = 2;
+    pub const MaxMetadataLength: u32 = 16;
+    pub const MaxExpiringPerBlock: u32 = 2;
+    pub const MaxAttesters: u32 = 2;
+    pub const MaxNamespaceMembers: u32 = 2;
+    pub const MaxReasonLength: u32 = 8;
+    pub static AllowReregistration: bool = false;
+    pub const MaxDocumentSize: u32 = 32 * 1024;
+    pub const DocumentFeePerByte: u128 = 1;
+    pub const FreeProofsPerEra: u32 = 2;
+    pub const FreeProofEraLength: u64 = 10;
 }
 
 impl system::Config for Test {
@@ -46,7 +64,7 @@ impl system::Config for Test {
     type BlockHashCount = BlockHashCount;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -54,12 +72,55 @@ impl system::Config for Test {
     type OnSetCode = ();
 }
 
+impl pallet_balances::Config for Test {
+    type MaxLocks = MaxLocks;
+    type MaxReserves = ();
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = MinimumPeriod;
+    type WeightInfo = ();
+}
+
 impl pallet_poe::Config for Test {
     type Event = Event;
-    type ProofLimit = ProofLimit;
+    type Currency = Balances;
+    type DepositPerByte = DepositPerByte;
+    type DepositMovesWithProof = DepositMovesWithProof;
+    type Time = Timestamp;
+    type MaxHistoryLength = MaxHistoryLength;
+    type MaxMetadataLength = MaxMetadataLength;
+    type MaxExpiringPerBlock = MaxExpiringPerBlock;
+    type MaxAttesters = MaxAttesters;
+    type MaxNamespaceMembers = MaxNamespaceMembers;
+    type MaxReasonLength = MaxReasonLength;
+    type AllowReregistration = AllowReregistration;
+    type MaxDocumentSize = MaxDocumentSize;
+    type DocumentFeePerByte = DocumentFeePerByte;
+    type DocumentFee = ();
+    type FreeProofsPerEra = FreeProofsPerEra;
+    type FreeProofEraLength = FreeProofEraLength;
+    type OffchainSignature = TestSignature;
+    type SigningPublicKey = UintAuthorityId;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+    let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(1, 1000), (2, 1000), (3, 1000), (4, 10)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    t.into()
 }
@@ -1,16 +1,47 @@
 use crate::mock::*;
 use super::*;
-use frame_support::{assert_ok, assert_noop};
+use codec::{Decode, Encode};
+use frame_support::{
+    assert_ok, assert_noop,
+    dispatch::{DispatchResultWithPostInfo, GetDispatchInfo},
+    traits::{Currency, Hooks},
+    Blake2_128Concat, StorageHasher,
+};
+use sp_runtime::testing::{TestSignature, UintAuthorityId};
+use sp_std::convert::TryInto;
+
+const ALGORITHM: HashAlgorithm = HashAlgorithm::Blake2_256;
+const DIGEST: [u8; 32] = [1; 32];
+const CLAIM: Claim = Claim { namespace: None, algorithm: ALGORITHM, digest: DIGEST };
+
+fn transfer(from: u64, to: u64, namespace: Option<NamespaceId>, digest: [u8; 32]) -> DispatchResultWithPostInfo {
+    PoeModule::offer_proof_transfer(Origin::signed(from), to, namespace, ALGORITHM, digest)?;
+    PoeModule::accept_proof_transfer(Origin::signed(to), namespace, ALGORITHM, digest)
+}
 
 #[test]
 fn create_proof_works() {
     new_test_ext().execute_with(|| {
-        let claim = vec![0, 1];
+        Timestamp::set_timestamp(42);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
 
-        assert_ok!(PoeModule::create_proof(Origin::signed(1), claim.clone()));
-        assert_eq!(
-            Proofs::<Test>::get(&claim),
-            Some((1, frame_system::Pallet::<Test>::block_number()))
+        let info = Proofs::<Test>::get(&CLAIM).unwrap();
+        assert_eq!(info.owner, 1);
+        assert_eq!(info.block_number, frame_system::Pallet::<Test>::block_number());
+        assert_eq!(info.created_at, Some(42));
+        assert_eq!(info.depositor, 1);
+        assert_eq!(info.deposit, PoeModule::deposit_for(&CLAIM, &info, None));
+        assert!(info.deposit > 0);
+        assert_eq!(Balances::reserved_balance(1), info.deposit);
+    })
+}
+
+#[test]
+fn create_proof_failed_when_deposit_cannot_be_reserved() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            PoeModule::create_proof(Origin::signed(4), None, ALGORITHM, DIGEST, None, None),
+            pallet_balances::Error::<Test>::InsufficientBalance
         );
     })
 }
@@ -18,24 +49,104 @@ fn create_proof_works() {
 #[test]
 fn create_proof_failed_when_proof_already_exist() {
     new_test_ext().execute_with(|| {
-        let claim = vec![0, 1];
-
-        assert_ok!(PoeModule::create_proof(Origin::signed(1), claim.clone()));
-        assert_noop!(PoeModule::create_proof(
-            Origin::signed(1), claim.clone()),
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_noop!(
+            PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None),
             Error::<Test>::ProofAlreadyExist
         );
     })
 }
 
 #[test]
-fn create_proof_failed_when_proof_exceeds_length_limit() {
+fn create_proof_with_same_digest_and_different_algorithm_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, HashAlgorithm::Blake2_256, DIGEST, None, None));
+        assert_ok!(PoeModule::create_proof(Origin::signed(2), None, HashAlgorithm::Sha2_256, DIGEST, None, None));
+        assert_ok!(PoeModule::create_proof(Origin::signed(3), None, HashAlgorithm::Keccak256, DIGEST, None, None));
+
+        let owner = |algorithm| Proofs::<Test>::get(&Claim { namespace: None, algorithm, digest: DIGEST }).unwrap().owner;
+        assert_eq!(owner(HashAlgorithm::Blake2_256), 1);
+        assert_eq!(owner(HashAlgorithm::Sha2_256), 2);
+        assert_eq!(owner(HashAlgorithm::Keccak256), 3);
+    })
+}
+
+fn metadata() -> ProofMetadataOf<Test> {
+    ProofMetadata {
+        title: b"contract".to_vec().try_into().unwrap(),
+        mime_type: b"application/pdf".to_vec().try_into().unwrap(),
+        uri: b"ipfs://Qm".to_vec().try_into().unwrap(),
+    }
+}
+
+#[test]
+fn create_proof_with_metadata_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, Some(metadata()), None));
+        assert_eq!(PoeModule::metadata(&CLAIM), Some(metadata()));
+
+        let info = Proofs::<Test>::get(&CLAIM).unwrap();
+        assert_eq!(info.deposit, PoeModule::deposit_for(&CLAIM, &info, Some(&metadata())));
+        assert!(info.deposit > PoeModule::deposit_for(&CLAIM, &info, None));
+        assert_eq!(Balances::reserved_balance(1), info.deposit);
+    })
+}
+
+#[test]
+fn create_proof_with_too_long_metadata_does_not_decode() {
+    // MaxMetadataLength is 16; create_proof is call 0
+    let call = |uri_length| (0u8, None::<NamespaceId>, ALGORITHM, DIGEST, Some((Vec::<u8>::new(), Vec::<u8>::new(), vec![0u8; uri_length])), None::<u64>).encode();
+    assert!(crate::Call::<Test>::decode(&mut &call(16)[..]).is_ok());
+    assert!(crate::Call::<Test>::decode(&mut &call(17)[..]).is_err());
+}
+
+#[test]
+fn storage_info_is_bounded() {
+    use frame_support::traits::StorageInfoTrait;
+    for info in PoeModule::storage_info() {
+        assert!(info.max_size.is_some(), "{:?} is unbounded", info);
+    }
+}
+
+#[test]
+fn set_metadata_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(PoeModule::set_metadata(Origin::signed(1), None, ALGORITHM, DIGEST, Some(metadata())));
+        assert_eq!(PoeModule::metadata(&CLAIM), Some(metadata()));
+
+        let info = Proofs::<Test>::get(&CLAIM).unwrap();
+        assert_eq!(info.deposit, PoeModule::deposit_for(&CLAIM, &info, Some(&metadata())));
+        assert_eq!(Balances::reserved_balance(1), info.deposit);
+
+        assert_ok!(PoeModule::set_metadata(Origin::signed(1), None, ALGORITHM, DIGEST, None));
+        assert_eq!(PoeModule::metadata(&CLAIM), None);
+        assert_eq!(Balances::reserved_balance(1), PoeModule::deposit_for(&CLAIM, &info, None));
+    })
+}
+
+#[test]
+fn set_metadata_moves_deposit_to_owner() {
     new_test_ext().execute_with(|| {
-        let claim: Vec<u8> = vec![0; 256];
+        DepositMovesWithProof::set(false);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(transfer(1, 2, None, DIGEST));
+        assert_ok!(PoeModule::set_metadata(Origin::signed(2), None, ALGORITHM, DIGEST, Some(metadata())));
+
+        let info = Proofs::<Test>::get(&CLAIM).unwrap();
+        assert_eq!(info.depositor, 2);
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::reserved_balance(2), info.deposit);
+    })
+}
 
+#[test]
+fn set_metadata_failed_when_caller_is_not_owner() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
         assert_noop!(
-            PoeModule::create_proof(Origin::signed(1), claim.clone()),
-            Error::<Test>::ProofExceedsLengthLimit
+            PoeModule::set_metadata(Origin::signed(2), None, ALGORITHM, DIGEST, Some(metadata())),
+            Error::<Test>::NotProofOwner
         );
     })
 }
@@ -43,21 +154,35 @@ fn create_proof_failed_when_proof_exceeds_length_limit() {
 #[test]
 fn revoke_proof_works() {
     new_test_ext().execute_with(|| {
-        let claim = vec![0, 1];
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(PoeModule::set_metadata(Origin::signed(1), None, ALGORITHM, DIGEST, Some(metadata())));
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+        assert_eq!(Proofs::<Test>::get(&CLAIM), None);
+        assert_eq!(PoeModule::metadata(&CLAIM), None);
+        // Only the tombstone's deposit is left reserved.
+        assert_eq!(Balances::reserved_balance(1), PoeModule::tombstone_deposit(&CLAIM));
+    })
+}
 
-        assert_ok!(PoeModule::create_proof(Origin::signed(1), claim.clone()));
-        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), claim.clone()));
-        assert_eq!(Proofs::<Test>::get(&claim), None);
+#[test]
+fn revoke_proof_failed_when_tombstone_deposit_cannot_be_paid() {
+    new_test_ext().execute_with(|| {
+        // account 4 holds 10, less than the tombstone deposit
+        DepositMovesWithProof::set(false);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(transfer(1, 4, None, DIGEST));
+        assert_noop!(
+            PoeModule::revoke_proof(Origin::signed(4), None, ALGORITHM, DIGEST, RevocationReason::Superseded),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
     })
 }
 
 #[test]
 fn revoke_proof_failed_when_proof_is_not_exist() {
     new_test_ext().execute_with(|| {
-        let claim = vec![0, 1];
-
         assert_noop!(
-            PoeModule::revoke_proof(Origin::signed(1), claim.clone()),
+            PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, DIGEST, RevocationReason::Superseded),
             Error::<Test>::ProofNotExist
         );
     })
@@ -66,36 +191,209 @@ fn revoke_proof_failed_when_proof_is_not_exist() {
 #[test]
 fn revoke_proof_failed_when_caller_is_not_owner() {
     new_test_ext().execute_with(|| {
-        let claim = vec![0, 1];
-
-        assert_ok!(PoeModule::create_proof(Origin::signed(1), claim.clone()));
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
         assert_noop!(
-            PoeModule::revoke_proof(Origin::signed(2), claim.clone()),
+            PoeModule::revoke_proof(Origin::signed(2), None, ALGORITHM, DIGEST, RevocationReason::Superseded),
             Error::<Test>::NotProofOwner
         );
     })
 }
 
+#[test]
+fn revoke_proof_leaves_tombstone() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        let reason = RevocationReason::Other(b"key lost".to_vec().try_into().unwrap());
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, DIGEST, reason.clone()));
+        assert_eq!(
+            PoeModule::tombstones(&CLAIM),
+            Some(Tombstone { revoked_by: 1, block_number: 3, reason, deposit: PoeModule::tombstone_deposit(&CLAIM) })
+        );
+
+        assert_noop!(
+            PoeModule::create_proof(Origin::signed(2), None, ALGORITHM, DIGEST, None, None),
+            Error::<Test>::ClaimRevoked
+        );
+    })
+}
+
+#[test]
+fn revoked_claim_can_be_registered_again_when_configured() {
+    new_test_ext().execute_with(|| {
+        AllowReregistration::set(true);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, DIGEST, RevocationReason::Erroneous));
+        assert_eq!(Balances::reserved_balance(1), PoeModule::tombstone_deposit(&CLAIM));
+        assert_ok!(PoeModule::create_proof(Origin::signed(2), None, ALGORITHM, DIGEST, None, None));
+        assert_eq!(PoeModule::tombstones(&CLAIM), None);
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Proofs::<Test>::get(&CLAIM).unwrap().owner, 2);
+    })
+}
+
+#[test]
+fn expired_proof_leaves_no_tombstone() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, Some(3)));
+        PoeModule::on_initialize(3);
+        assert_eq!(PoeModule::tombstones(&CLAIM), None);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+    })
+}
+
 #[test]
 fn transfer_proof_works() {
     new_test_ext().execute_with(|| {
-        let claim = vec![0, 1];
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        let deposit = Proofs::<Test>::get(&CLAIM).unwrap().deposit;
+        assert_ok!(transfer(1, 2, None, DIGEST));
 
-        assert_ok!(PoeModule::create_proof(Origin::signed(1), claim.clone()));
-        assert_eq!(Proofs::<Test>::get(&claim).unwrap(), (1, 0));
-        assert_ok!(PoeModule::transfer_proof(Origin::signed(1), 2, claim.clone()));
-        assert_eq!(Proofs::<Test>::get(&claim).unwrap(), (2, 0));
+        let info = Proofs::<Test>::get(&CLAIM).unwrap();
+        assert_eq!((info.owner, info.depositor), (2, 2));
+        assert_eq!(Balances::reserved_balance(1), 0);
+        assert_eq!(Balances::reserved_balance(2), deposit);
     })
 }
 
 #[test]
-fn transfer_proof_failed_when_caller_is_not_owner() {
+fn transfer_proof_waits_for_acceptance() {
     new_test_ext().execute_with(|| {
-        let claim = vec![0, 1];
+        System::set_block_number(1);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(PoeModule::offer_proof_transfer(Origin::signed(1), 2, None, ALGORITHM, DIGEST));
+        System::assert_last_event(crate::mock::Event::PoeModule(crate::Event::TransferOffered(1, 2, CLAIM)));
+        assert_eq!(PoeModule::pending_transfers(&CLAIM), Some(2));
+        assert_eq!(Proofs::<Test>::get(&CLAIM).unwrap().owner, 1);
+
+        assert_noop!(
+            PoeModule::accept_proof_transfer(Origin::signed(3), None, ALGORITHM, DIGEST),
+            Error::<Test>::NotTransferRecipient
+        );
+        assert_ok!(PoeModule::accept_proof_transfer(Origin::signed(2), None, ALGORITHM, DIGEST));
+        System::assert_last_event(crate::mock::Event::PoeModule(crate::Event::ProofTransferred(2, CLAIM)));
+        assert_eq!(PoeModule::pending_transfers(&CLAIM), None);
+        assert_eq!(Proofs::<Test>::get(&CLAIM).unwrap().owner, 2);
+        assert_noop!(
+            PoeModule::accept_proof_transfer(Origin::signed(2), None, ALGORITHM, DIGEST),
+            Error::<Test>::NoPendingTransfer
+        );
+    })
+}
 
-        assert_ok!(PoeModule::create_proof(Origin::signed(1), claim.clone()));
+#[test]
+fn cancel_proof_transfer_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
         assert_noop!(
-            PoeModule::transfer_proof(Origin::signed(2), 3, claim.clone()),
+            PoeModule::cancel_proof_transfer(Origin::signed(1), None, ALGORITHM, DIGEST),
+            Error::<Test>::NoPendingTransfer
+        );
+        assert_ok!(PoeModule::offer_proof_transfer(Origin::signed(1), 2, None, ALGORITHM, DIGEST));
+        assert_noop!(
+            PoeModule::cancel_proof_transfer(Origin::signed(2), None, ALGORITHM, DIGEST),
+            Error::<Test>::NotProofOwner
+        );
+
+        assert_ok!(PoeModule::cancel_proof_transfer(Origin::signed(1), None, ALGORITHM, DIGEST));
+        System::assert_last_event(crate::mock::Event::PoeModule(crate::Event::TransferCancelled(1, CLAIM)));
+        assert_noop!(
+            PoeModule::accept_proof_transfer(Origin::signed(2), None, ALGORITHM, DIGEST),
+            Error::<Test>::NoPendingTransfer
+        );
+    })
+}
+
+#[test]
+fn revoke_proof_drops_pending_transfer() {
+    new_test_ext().execute_with(|| {
+        AllowReregistration::set(true);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(PoeModule::offer_proof_transfer(Origin::signed(1), 2, None, ALGORITHM, DIGEST));
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_noop!(
+            PoeModule::accept_proof_transfer(Origin::signed(2), None, ALGORITHM, DIGEST),
+            Error::<Test>::NoPendingTransfer
+        );
+    })
+}
+
+#[test]
+fn transfer_proof_keeps_creation_block_and_records_provenance() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        Timestamp::set_timestamp(42);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+
+        System::set_block_number(5);
+        assert_ok!(transfer(1, 2, None, DIGEST));
+        System::set_block_number(7);
+        assert_ok!(transfer(2, 3, None, DIGEST));
+
+        let info = Proofs::<Test>::get(&CLAIM).unwrap();
+        assert_eq!((info.owner, info.block_number, info.created_at), (3, 1, Some(42)));
+        assert_eq!(
+            PoeModule::proof_history(&CLAIM).into_inner(),
+            vec![
+                TransferRecord { from: 1, to: 2, block_number: 5 },
+                TransferRecord { from: 2, to: 3, block_number: 7 },
+            ]
+        );
+
+        // MaxHistoryLength is 2, the oldest transfer is dropped
+        System::set_block_number(9);
+        assert_ok!(transfer(3, 1, None, DIGEST));
+        assert_eq!(
+            PoeModule::proof_history(&CLAIM).into_inner(),
+            vec![
+                TransferRecord { from: 2, to: 3, block_number: 7 },
+                TransferRecord { from: 3, to: 1, block_number: 9 },
+            ]
+        );
+
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+        assert!(PoeModule::proof_history(&CLAIM).is_empty());
+    })
+}
+
+#[test]
+fn transfer_proof_keeps_deposit_with_payer_when_configured() {
+    new_test_ext().execute_with(|| {
+        DepositMovesWithProof::set(false);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        let deposit = Proofs::<Test>::get(&CLAIM).unwrap().deposit;
+        assert_ok!(transfer(1, 2, None, DIGEST));
+
+        let info = Proofs::<Test>::get(&CLAIM).unwrap();
+        assert_eq!((info.owner, info.depositor), (2, 1));
+        assert_eq!(Balances::reserved_balance(1), deposit);
+        assert_eq!(Balances::reserved_balance(2), 0);
+
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(2), None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+        assert_eq!(Balances::reserved_balance(1), 0);
+    })
+}
+
+#[test]
+fn transfer_proof_failed_when_recipient_cannot_take_deposit() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(PoeModule::offer_proof_transfer(Origin::signed(1), 4, None, ALGORITHM, DIGEST));
+        assert_noop!(
+            PoeModule::accept_proof_transfer(Origin::signed(4), None, ALGORITHM, DIGEST),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    })
+}
+
+#[test]
+fn transfer_proof_failed_when_caller_is_not_owner() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_noop!(
+            PoeModule::offer_proof_transfer(Origin::signed(2), 3, None, ALGORITHM, DIGEST),
             Error::<Test>::NotProofOwner
         );
     })
@@ -104,11 +402,717 @@ fn transfer_proof_failed_when_caller_is_not_owner() {
 #[test]
 fn transfer_proof_failed_when_proof_not_exist() {
     new_test_ext().execute_with(|| {
-        let claim = vec![0, 1];
+        assert_noop!(
+            PoeModule::offer_proof_transfer(Origin::signed(1), 2, None, ALGORITHM, DIGEST),
+            Error::<Test>::ProofNotExist
+        );
+    })
+}
+
+#[test]
+fn expired_proof_is_removed_and_refunded() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, Some(metadata()), Some(3)));
+        assert_eq!(PoeModule::expiry(&CLAIM), Some(3));
+        assert!(Balances::reserved_balance(1) > 0);
+
+        PoeModule::on_initialize(2);
+        assert!(Proofs::<Test>::contains_key(&CLAIM));
+
+        PoeModule::on_initialize(3);
+        assert_eq!(Proofs::<Test>::get(&CLAIM), None);
+        assert_eq!(PoeModule::metadata(&CLAIM), None);
+        assert_eq!(PoeModule::expiry(&CLAIM), None);
+        assert!(PoeModule::expiring(3).is_empty());
+        assert_eq!(Balances::reserved_balance(1), 0);
+        System::assert_last_event(crate::mock::Event::PoeModule(crate::Event::ProofExpired(1, CLAIM)));
+    })
+}
+
+#[test]
+fn create_proof_failed_when_expiry_is_invalid() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(5);
+        assert_noop!(
+            PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, Some(5)),
+            Error::<Test>::InvalidExpiry
+        );
+    })
+}
 
+#[test]
+fn create_proof_failed_when_too_many_expiring() {
+    new_test_ext().execute_with(|| {
+        // MaxExpiringPerBlock is 2
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, [1; 32], None, Some(3)));
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, [2; 32], None, Some(3)));
+        assert_noop!(
+            PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, [3; 32], None, Some(3)),
+            Error::<Test>::TooManyExpiring
+        );
+    })
+}
+
+#[test]
+fn extend_expiry_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, Some(3)));
+        assert_noop!(
+            PoeModule::extend_expiry(Origin::signed(1), None, ALGORITHM, DIGEST, Some(2)),
+            Error::<Test>::InvalidExpiry
+        );
+        assert_noop!(
+            PoeModule::extend_expiry(Origin::signed(2), None, ALGORITHM, DIGEST, Some(5)),
+            Error::<Test>::NotProofOwner
+        );
+
+        assert_ok!(PoeModule::extend_expiry(Origin::signed(1), None, ALGORITHM, DIGEST, Some(5)));
+        assert_eq!(PoeModule::expiry(&CLAIM), Some(5));
+        PoeModule::on_initialize(3);
+        assert!(Proofs::<Test>::contains_key(&CLAIM));
+
+        assert_ok!(PoeModule::extend_expiry(Origin::signed(1), None, ALGORITHM, DIGEST, None));
+        assert_eq!(PoeModule::expiry(&CLAIM), None);
+        PoeModule::on_initialize(5);
+        assert!(Proofs::<Test>::contains_key(&CLAIM));
+    })
+}
+
+#[test]
+fn revoke_proof_unschedules_expiry() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, Some(3)));
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+        assert!(PoeModule::expiring(3).is_empty());
+        assert_eq!(PoeModule::expiry(&CLAIM), None);
+    })
+}
+
+#[test]
+fn batch_proof_inclusion_works() {
+    new_test_ext().execute_with(|| {
+        let digests = [[1u8; 32], [2; 32], [3; 32], [4; 32]];
+        let leaves: Vec<_> = digests.iter().map(|digest| ALGORITHM.hash_leaf(digest)).collect();
+        let left = ALGORITHM.hash_pair(&leaves[0], &leaves[1]);
+        let right = ALGORITHM.hash_pair(&leaves[2], &leaves[3]);
+        let root = ALGORITHM.hash_pair(&left, &right);
+        assert_eq!(ALGORITHM.merkle_root(&digests), Some(root));
+
+        assert_eq!(PoeModule::verify_inclusion(None, ALGORITHM, root, digests[2], vec![leaves[3], left]), None);
+        assert_ok!(PoeModule::create_batch_proof(Origin::signed(1), None, ALGORITHM, root, 4, None, None));
+
+        let info = PoeModule::verify_inclusion(None, ALGORITHM, root, digests[2], vec![leaves[3], left]).unwrap();
+        assert_eq!(info.owner, 1);
+        assert_eq!(info.block_number, frame_system::Pallet::<Test>::block_number());
+        assert!(PoeModule::verify_inclusion(None, ALGORITHM, root, digests[0], vec![leaves[1], right]).is_some());
+
+        // wrong sibling, wrong leaf, internal node passed off as a leaf
+        assert_eq!(PoeModule::verify_inclusion(None, ALGORITHM, root, digests[2], vec![leaves[0], left]), None);
+        assert_eq!(PoeModule::verify_inclusion(None, ALGORITHM, root, [5; 32], vec![leaves[3], left]), None);
+        assert_eq!(PoeModule::verify_inclusion(None, ALGORITHM, root, left, vec![right]), None);
+        assert_eq!(PoeModule::verify_inclusion(None, ALGORITHM, root, leaves[2], vec![leaves[3], left]), None);
+
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, root, RevocationReason::Superseded));
+        assert_eq!(PoeModule::batches(&Claim { namespace: None, algorithm: ALGORITHM, digest: root }), None);
+        assert_eq!(PoeModule::verify_inclusion(None, ALGORITHM, root, digests[2], vec![leaves[3], left]), None);
+    })
+}
+
+#[test]
+fn batch_proof_paths_must_match_the_depth() {
+    new_test_ext().execute_with(|| {
+        // The odd last leaf is paired with itself.
+        let digests = [[1u8; 32], [2; 32], [3; 32]];
+        let leaves: Vec<_> = digests.iter().map(|digest| ALGORITHM.hash_leaf(digest)).collect();
+        let left = ALGORITHM.hash_pair(&leaves[0], &leaves[1]);
+        let right = ALGORITHM.hash_pair(&leaves[2], &leaves[2]);
+        let root = ALGORITHM.hash_pair(&left, &right);
+        assert_eq!(ALGORITHM.merkle_root(&digests), Some(root));
+        assert_eq!(crate::batch_depth(3), 2);
+
+        assert_ok!(PoeModule::create_batch_proof(Origin::signed(1), None, ALGORITHM, root, 3, None, None));
+        assert!(PoeModule::verify_inclusion(None, ALGORITHM, root, digests[2], vec![leaves[2], left]).is_some());
+        // Right hashes, but a batch of three is two levels deep.
+        assert_eq!(PoeModule::verify_inclusion(None, ALGORITHM, root, digests[2], vec![]), None);
+
+        let single = ALGORITHM.merkle_root(&[[9u8; 32]]).unwrap();
+        assert_ok!(PoeModule::create_batch_proof(Origin::signed(1), None, ALGORITHM, single, 1, None, None));
+        assert!(PoeModule::verify_inclusion(None, ALGORITHM, single, [9; 32], vec![]).is_some());
+        assert_eq!(PoeModule::verify_inclusion(None, ALGORITHM, single, [9; 32], vec![single]), None);
+    })
+}
+
+#[test]
+fn create_batch_proof_failed_when_batch_is_empty() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            PoeModule::create_batch_proof(Origin::signed(1), None, ALGORITHM, DIGEST, 0, None, None),
+            Error::<Test>::EmptyBatch
+        );
+    })
+}
+
+#[test]
+fn attest_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(PoeModule::set_required_attesters(Origin::signed(1), None, ALGORITHM, DIGEST, vec![2, 3].try_into().unwrap()));
+        assert!(!PoeModule::is_complete(&CLAIM));
+
+        assert_ok!(PoeModule::attest(Origin::signed(2), None, ALGORITHM, DIGEST));
+        assert!(!PoeModule::is_complete(&CLAIM));
+        assert_ok!(PoeModule::attest(Origin::signed(3), None, ALGORITHM, DIGEST));
+        assert!(PoeModule::is_complete(&CLAIM));
+        assert_eq!(PoeModule::attestations(&CLAIM).into_inner(), vec![(2, 3), (3, 3)]);
+        System::assert_last_event(crate::mock::Event::PoeModule(crate::Event::ProofCompleted(CLAIM)));
+
+        assert_ok!(PoeModule::revoke_attestation(Origin::signed(2), None, ALGORITHM, DIGEST));
+        assert!(!PoeModule::is_complete(&CLAIM));
+        assert_eq!(PoeModule::attestations(&CLAIM).into_inner(), vec![(3, 3)]);
+    })
+}
+
+#[test]
+fn attest_failed_when_invalid() {
+    new_test_ext().execute_with(|| {
         assert_noop!(
-            PoeModule::transfer_proof(Origin::signed(1), 2, claim.clone()),
+            PoeModule::attest(Origin::signed(2), None, ALGORITHM, DIGEST),
             Error::<Test>::ProofNotExist
         );
+
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_noop!(
+            PoeModule::attest(Origin::signed(2), None, ALGORITHM, DIGEST),
+            Error::<Test>::NotRequiredAttester
+        );
+
+        assert_ok!(PoeModule::set_required_attesters(Origin::signed(1), None, ALGORITHM, DIGEST, vec![2, 3].try_into().unwrap()));
+        assert_ok!(PoeModule::attest(Origin::signed(2), None, ALGORITHM, DIGEST));
+        assert_noop!(
+            PoeModule::attest(Origin::signed(2), None, ALGORITHM, DIGEST),
+            Error::<Test>::AlreadyAttested
+        );
+        assert_noop!(
+            PoeModule::attest(Origin::signed(4), None, ALGORITHM, DIGEST),
+            Error::<Test>::NotRequiredAttester
+        );
+        assert_noop!(
+            PoeModule::revoke_attestation(Origin::signed(4), None, ALGORITHM, DIGEST),
+            Error::<Test>::NotAttested
+        );
+    })
+}
+
+#[test]
+fn set_required_attesters_drops_other_attestations() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(3);
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(PoeModule::set_required_attesters(Origin::signed(1), None, ALGORITHM, DIGEST, vec![2, 3].try_into().unwrap()));
+        assert_ok!(PoeModule::attest(Origin::signed(2), None, ALGORITHM, DIGEST));
+        assert_ok!(PoeModule::attest(Origin::signed(3), None, ALGORITHM, DIGEST));
+
+        assert_ok!(PoeModule::set_required_attesters(Origin::signed(1), None, ALGORITHM, DIGEST, vec![3, 4].try_into().unwrap()));
+        assert_eq!(PoeModule::attestations(&CLAIM).into_inner(), vec![(3, 3)]);
+        assert!(!PoeModule::is_complete(&CLAIM));
+
+        assert_ok!(PoeModule::set_required_attesters(Origin::signed(1), None, ALGORITHM, DIGEST, vec![4].try_into().unwrap()));
+        assert!(!Attestations::<Test>::contains_key(&CLAIM));
+    })
+}
+
+#[test]
+fn set_required_attesters_failed_when_invalid() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_noop!(
+            PoeModule::set_required_attesters(Origin::signed(2), None, ALGORITHM, DIGEST, vec![3].try_into().unwrap()),
+            Error::<Test>::NotProofOwner
+        );
+    })
+}
+
+#[test]
+fn revoke_proof_clears_attestations() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert_ok!(PoeModule::set_required_attesters(Origin::signed(1), None, ALGORITHM, DIGEST, vec![2].try_into().unwrap()));
+        assert_ok!(PoeModule::attest(Origin::signed(2), None, ALGORITHM, DIGEST));
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+
+        assert!(PoeModule::attestations(&CLAIM).is_empty());
+        assert!(PoeModule::required_attesters(&CLAIM).is_empty());
+    })
+}
+
+#[test]
+fn create_signed_proof_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let signature = TestSignature(7, PoeModule::signed_proof_payload(&1, &CLAIM));
+        assert_ok!(PoeModule::create_signed_proof(
+            Origin::signed(1), None, ALGORITHM, DIGEST, UintAuthorityId(7), signature.clone(), None, None
+        ));
+        System::assert_last_event(crate::mock::Event::PoeModule(crate::Event::SignedProofCreated(1, CLAIM, 7)));
+        assert_eq!(Proofs::<Test>::get(&CLAIM).unwrap().owner, 1);
+        assert_eq!(PoeModule::signers(&CLAIM), Some(7));
+
+        assert!(PoeModule::check_signature(&CLAIM, &1, &signature));
+        assert!(!PoeModule::check_signature(&CLAIM, &2, &signature));
+        assert!(!PoeModule::check_signature(&CLAIM, &1, &TestSignature(8, PoeModule::signed_proof_payload(&1, &CLAIM))));
+        assert!(!PoeModule::check_signature(&CLAIM, &1, &TestSignature(7, DIGEST.to_vec())));
+
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+        assert_eq!(PoeModule::signers(&CLAIM), None);
+        assert!(!PoeModule::check_signature(&CLAIM, &1, &signature));
+    })
+}
+
+#[test]
+fn create_signed_proof_failed_when_signature_is_invalid() {
+    new_test_ext().execute_with(|| {
+        let payload = PoeModule::signed_proof_payload(&1, &CLAIM);
+        assert_noop!(
+            PoeModule::create_signed_proof(
+                Origin::signed(1), None, ALGORITHM, DIGEST, UintAuthorityId(7), TestSignature(8, payload.clone()), None, None
+            ),
+            Error::<Test>::BadSignature
+        );
+        // A bare signature of the digest is not enough.
+        assert_noop!(
+            PoeModule::create_signed_proof(
+                Origin::signed(1), None, ALGORITHM, DIGEST, UintAuthorityId(7), TestSignature(7, DIGEST.to_vec()), None, None
+            ),
+            Error::<Test>::BadSignature
+        );
+        // Nor is a signature of the same digest under another algorithm.
+        assert_noop!(
+            PoeModule::create_signed_proof(
+                Origin::signed(1), None, HashAlgorithm::Keccak256, DIGEST, UintAuthorityId(7), TestSignature(7, payload.clone()), None, None
+            ),
+            Error::<Test>::BadSignature
+        );
+        // Nor can another sender submit a signature copied from the pool as their own.
+        assert_noop!(
+            PoeModule::create_signed_proof(
+                Origin::signed(2), None, ALGORITHM, DIGEST, UintAuthorityId(7), TestSignature(7, payload), None, None
+            ),
+            Error::<Test>::BadSignature
+        );
+    })
+}
+
+#[test]
+fn unsigned_proofs_match_no_signature() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, DIGEST, None, None));
+        assert!(!PoeModule::check_signature(&CLAIM, &1, &TestSignature(1, PoeModule::signed_proof_payload(&1, &CLAIM))));
+    })
+}
+
+#[test]
+fn store_document_writes_chunks_to_offchain_storage() {
+    let data = vec![7u8; DOCUMENT_CHUNK_SIZE + 10];
+    let claim = Claim { digest: ALGORITHM.hash(&data), ..CLAIM };
+    let mut ext = new_test_ext();
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        let _ = Balances::deposit_creating(&1, 1_000_000);
+        let free = Balances::free_balance(1);
+        assert_ok!(PoeModule::store_document(
+            Origin::signed(1), None, ALGORITHM, data.clone().try_into().unwrap(), None, None
+        ));
+        System::assert_last_event(crate::mock::Event::PoeModule(
+            crate::Event::DocumentStored(1, claim, data.len() as u32)
+        ));
+        assert_eq!(PoeModule::documents(&claim), Some(data.len() as u32));
+
+        let info = Proofs::<Test>::get(&claim).unwrap();
+        assert_eq!(Balances::reserved_balance(1), info.deposit);
+        assert_eq!(free - Balances::free_balance(1), info.deposit + data.len() as u128);
+    });
+    ext.persist_offchain_overlay();
+
+    let db = ext.offchain_db();
+    assert_eq!(db.get(&PoeModule::document_chunk_key(&claim, 0)), Some(data[..DOCUMENT_CHUNK_SIZE].to_vec()));
+    assert_eq!(db.get(&PoeModule::document_chunk_key(&claim, 1)), Some(data[DOCUMENT_CHUNK_SIZE..].to_vec()));
+    assert_eq!(db.get(&PoeModule::document_chunk_key(&claim, 2)), None);
+
+    ext.execute_with(|| {
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(1), None, ALGORITHM, claim.digest, RevocationReason::Superseded));
+        assert_eq!(PoeModule::documents(&claim), None);
+    });
+    ext.persist_offchain_overlay();
+    assert_eq!(ext.offchain_db().get(&PoeModule::document_chunk_key(&claim, 0)), None);
+}
+
+#[test]
+fn store_document_failed_when_fee_cannot_be_paid() {
+    new_test_ext().execute_with(|| {
+        // account 1 holds 1000, less than the deposit and the fee for 1000 bytes
+        assert_noop!(
+            PoeModule::store_document(Origin::signed(1), None, ALGORITHM, vec![7u8; 1000].try_into().unwrap(), None, None),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+    })
+}
+
+#[test]
+fn oversized_document_does_not_decode() {
+    // MaxDocumentSize is 32 KiB; store_document is call 3
+    let call = |size| (3u8, None::<NamespaceId>, ALGORITHM, vec![0u8; size], None::<()>, None::<u64>).encode();
+    assert!(crate::Call::<Test>::decode(&mut &call(32 * 1024)[..]).is_ok());
+    assert!(crate::Call::<Test>::decode(&mut &call(32 * 1024 + 1)[..]).is_err());
+}
+
+#[test]
+fn store_document_weight_grows_with_size() {
+    let weight = |size| {
+        crate::Call::<Test>::store_document(None, ALGORITHM, vec![0u8; size].try_into().unwrap(), None, None)
+            .get_dispatch_info()
+            .weight
+    };
+    assert!(weight(1024) > weight(0));
+    assert!(weight(32 * 1024) > weight(16 * 1024));
+}
+
+#[test]
+fn create_proof_for_works() {
+    new_test_ext().execute_with(|| {
+        // account 5 holds no tokens, account 2 relays and pays the deposit
+        let payload = PoeModule::create_proof_payload(&5, &CLAIM, 0);
+        let signature = TestSignature(5, payload);
+        assert_ok!(PoeModule::create_proof_for(Origin::signed(2), 5, None, ALGORITHM, DIGEST, signature, 0));
+
+        let info = Proofs::<Test>::get(&CLAIM).unwrap();
+        assert_eq!((info.owner, info.depositor), (5, 2));
+        assert_eq!(Balances::reserved_balance(2), info.deposit);
+        assert_eq!(PoeModule::signer_nonce(5), 1);
+
+        // Revoking reserves a deposit for the tombstone, so the owner needs funds of their own.
+        let _ = Balances::deposit_creating(&5, 1000);
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(5), None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+        assert_eq!(Balances::reserved_balance(2), 0);
+    })
+}
+
+#[test]
+fn create_proof_for_failed_when_signature_is_invalid() {
+    new_test_ext().execute_with(|| {
+        let payload = PoeModule::create_proof_payload(&5, &CLAIM, 0);
+        assert_noop!(
+            PoeModule::create_proof_for(Origin::signed(2), 5, None, ALGORITHM, DIGEST, TestSignature(6, payload), 0),
+            Error::<Test>::BadSignature
+        );
+
+        let payload = PoeModule::create_proof_payload(&5, &Claim { digest: [2; 32], ..CLAIM }, 0);
+        assert_noop!(
+            PoeModule::create_proof_for(Origin::signed(2), 5, None, ALGORITHM, DIGEST, TestSignature(5, payload), 0),
+            Error::<Test>::BadSignature
+        );
+    })
+}
+
+#[test]
+fn create_proof_for_failed_when_replayed() {
+    new_test_ext().execute_with(|| {
+        let signature = TestSignature(5, PoeModule::create_proof_payload(&5, &CLAIM, 0));
+        assert_ok!(PoeModule::create_proof_for(Origin::signed(2), 5, None, ALGORITHM, DIGEST, signature.clone(), 0));
+        let _ = Balances::deposit_creating(&5, 1000);
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(5), None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+
+        assert_noop!(
+            PoeModule::create_proof_for(Origin::signed(2), 5, None, ALGORITHM, DIGEST, signature, 0),
+            Error::<Test>::BadNonce
+        );
+    })
+}
+
+#[test]
+fn namespace_members_create_proofs() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(PoeModule::register_namespace(Origin::signed(1), vec![2].try_into().unwrap()));
+        System::assert_last_event(crate::mock::Event::PoeModule(crate::Event::NamespaceRegistered(1, 0)));
+        assert_eq!(Balances::reserved_balance(1), PoeModule::namespace_deposit());
+
+        assert_ok!(PoeModule::create_proof(Origin::signed(2), Some(0), ALGORITHM, DIGEST, None, None));
+        assert_noop!(
+            PoeModule::create_proof(Origin::signed(3), Some(0), ALGORITHM, [2; 32], None, None),
+            Error::<Test>::NotNamespaceMember
+        );
+        assert_noop!(
+            PoeModule::create_proof(Origin::signed(2), Some(1), ALGORITHM, DIGEST, None, None),
+            Error::<Test>::NamespaceNotExist
+        );
+
+        // The same digest can be registered once per namespace and once globally.
+        assert_noop!(
+            PoeModule::create_proof(Origin::signed(1), Some(0), ALGORITHM, DIGEST, None, None),
+            Error::<Test>::ProofAlreadyExist
+        );
+        assert_ok!(PoeModule::create_proof(Origin::signed(3), None, ALGORITHM, DIGEST, None, None));
+        assert_eq!(PoeModule::proofs(&Claim { namespace: Some(0), ..CLAIM }).unwrap().owner, 2);
+        assert_eq!(PoeModule::proofs(&CLAIM).unwrap().owner, 3);
+    })
+}
+
+#[test]
+fn namespace_proofs_only_move_to_members() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(PoeModule::register_namespace(Origin::signed(1), vec![2].try_into().unwrap()));
+        assert_ok!(PoeModule::create_proof(Origin::signed(2), Some(0), ALGORITHM, DIGEST, None, None));
+        assert_noop!(
+            PoeModule::offer_proof_transfer(Origin::signed(2), 3, Some(0), ALGORITHM, DIGEST),
+            Error::<Test>::NotNamespaceMember
+        );
+        assert_ok!(transfer(2, 1, Some(0), DIGEST));
+    })
+}
+
+#[test]
+fn set_namespace_members_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_ok!(PoeModule::register_namespace(Origin::signed(1), vec![2].try_into().unwrap()));
+        assert_noop!(
+            PoeModule::set_namespace_members(Origin::signed(2), 0, vec![2, 3].try_into().unwrap()),
+            Error::<Test>::NotNamespaceOwner
+        );
+        assert_noop!(
+            PoeModule::set_namespace_members(Origin::signed(1), 1, vec![3].try_into().unwrap()),
+            Error::<Test>::NamespaceNotExist
+        );
+
+        assert_ok!(PoeModule::set_namespace_members(Origin::signed(1), 0, vec![3].try_into().unwrap()));
+        System::assert_last_event(crate::mock::Event::PoeModule(crate::Event::NamespaceMembersSet(1, 0)));
+        assert_ok!(PoeModule::create_proof(Origin::signed(3), Some(0), ALGORITHM, DIGEST, None, None));
+        assert_noop!(
+            PoeModule::create_proof(Origin::signed(2), Some(0), ALGORITHM, [2; 32], None, None),
+            Error::<Test>::NotNamespaceMember
+        );
+    })
+}
+
+#[test]
+fn owner_index_follows_proofs() {
+    new_test_ext().execute_with(|| {
+        let claim = |digest| Claim { namespace: None, algorithm: ALGORITHM, digest };
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, [1; 32], None, None));
+        assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, [2; 32], None, Some(3)));
+        assert_ok!(PoeModule::create_proof(Origin::signed(2), None, ALGORITHM, [3; 32], None, None));
+        assert_eq!(PoeModule::proof_count(), 3);
+        assert_eq!(PoeModule::proofs_of(1, 0).len(), 2);
+        assert_eq!(PoeModule::proofs_of(1, 1), vec![]);
+
+        assert_ok!(transfer(1, 2, None, [1; 32]));
+        let owned = |owner| {
+            let mut claims: Vec<_> = PoeModule::proofs_of(owner, 0).into_iter().map(|(claim, _)| claim).collect();
+            claims.sort_by_key(|claim| claim.digest);
+            claims
+        };
+        assert_eq!(owned(1), vec![claim([2; 32])]);
+        assert_eq!(owned(2), vec![claim([1; 32]), claim([3; 32])]);
+
+        assert_ok!(PoeModule::revoke_proof(Origin::signed(2), None, ALGORITHM, [3; 32], RevocationReason::Superseded));
+        PoeModule::on_initialize(3);
+        assert_eq!(owned(1), vec![]);
+        assert_eq!(owned(2), vec![claim([1; 32])]);
+        assert_eq!(PoeModule::proof_count(), 1);
+    })
+}
+
+#[test]
+fn proofs_of_is_paginated() {
+    new_test_ext().execute_with(|| {
+        let _ = Balances::deposit_creating(&1, 1_000_000);
+        for i in 0..(PROOFS_PAGE_SIZE as u8 + 1) {
+            assert_ok!(PoeModule::create_proof(Origin::signed(1), None, ALGORITHM, [i; 32], None, None));
+        }
+        assert_eq!(PoeModule::proofs_of(1, 0).len(), PROOFS_PAGE_SIZE);
+        assert_eq!(PoeModule::proofs_of(1, 1).len(), 1);
+        assert_eq!(PoeModule::proofs_of(1, u32::MAX), vec![]);
+    })
+}
+
+#[test]
+fn migration_rekeys_raw_claims_without_deposit() {
+    new_test_ext().execute_with(|| {
+        let raw = vec![0u8, 1];
+        frame_support::storage::migration::put_storage_value(
+            b"PoeModule",
+            b"Proofs",
+            &Blake2_128Concat::hash(&raw.encode()),
+            (1u64, 5u64),
+        );
+
+        crate::migrations::migrate::<Test>();
+
+        let claim = Claim {
+            namespace: None,
+            algorithm: HashAlgorithm::Blake2_256,
+            digest: sp_io::hashing::blake2_256(&raw),
+        };
+        assert_eq!(
+            Proofs::<Test>::get(&claim),
+            Some(ProofInfo { owner: 1, block_number: 5, created_at: None, depositor: 1, deposit: 0 })
+        );
+        assert_eq!(Proofs::<Test>::iter().count(), 1);
+        assert_eq!(PoeModule::proofs_of(1, 0), vec![(claim, Proofs::<Test>::get(&claim).unwrap())]);
+        assert_eq!(PoeModule::proof_count(), 1);
+    })
+}
+
+#[test]
+fn migration_keeps_32_byte_raw_claims() {
+    new_test_ext().execute_with(|| {
+        let raw = [7u8; 32].to_vec();
+        frame_support::storage::migration::put_storage_value(
+            b"PoeModule",
+            b"Proofs",
+            &Blake2_128Concat::hash(&raw.encode()),
+            (1u64, 5u64),
+        );
+
+        crate::migrations::migrate::<Test>();
+
+        let claim = Claim { namespace: None, algorithm: HashAlgorithm::Blake2_256, digest: [7u8; 32] };
+        assert_eq!(Proofs::<Test>::get(&claim).map(|info| info.owner), Some(1));
+        assert_eq!(Proofs::<Test>::iter().count(), 1);
+    })
+}
+
+#[test]
+fn migration_moves_claims_to_the_global_namespace() {
+    new_test_ext().execute_with(|| {
+        use frame_support::{traits::StorageVersion, Twox64Concat};
+        use frame_support::storage::migration::put_storage_value;
+
+        StorageVersion::new(4).put::<PoeModule>();
+        let old_claim = (ALGORITHM, DIGEST);
+        let old = crate::migrations::v3::V3ProofInfo { owner: 1u64, block_number: 5u64, created_at: 0u64, depositor: 1u64, deposit: 0u128 };
+        put_storage_value(b"PoeModule", b"Proofs", &Blake2_128Concat::hash(&old_claim.encode()), old);
+        put_storage_value(b"PoeModule", b"Expiry", &Blake2_128Concat::hash(&old_claim.encode()), 3u64);
+        put_storage_value(b"PoeModule", b"Expiring", &Twox64Concat::hash(&3u64.encode()), vec![old_claim]);
+
+        crate::migrations::migrate::<Test>();
+
+        let info = ProofInfo { owner: 1, block_number: 5, created_at: None, depositor: 1, deposit: 0 };
+        assert_eq!(Proofs::<Test>::get(&CLAIM), Some(info.clone()));
+        assert_eq!(Proofs::<Test>::iter().count(), 1);
+        assert_eq!(PoeModule::expiry(&CLAIM), Some(3));
+        assert_eq!(PoeModule::expiring(3).into_inner(), vec![CLAIM]);
+        assert_eq!(PoeModule::proofs_of(1, 0), vec![(CLAIM, info)]);
+        assert_eq!(StorageVersion::get::<PoeModule>(), 7);
+    })
+}
+
+#[test]
+fn migration_gives_tombstones_a_zero_deposit() {
+    new_test_ext().execute_with(|| {
+        use frame_support::{storage::migration::put_storage_value, traits::StorageVersion};
+
+        StorageVersion::new(5).put::<PoeModule>();
+        let reason: RevocationReasonOf<Test> = RevocationReason::Erroneous;
+        put_storage_value(b"PoeModule", b"Tombstones", &Blake2_128Concat::hash(&CLAIM.encode()), (1u64, 3u64, reason.clone()));
+
+        crate::migrations::migrate::<Test>();
+
+        assert_eq!(
+            PoeModule::tombstones(&CLAIM),
+            Some(Tombstone { revoked_by: 1, block_number: 3, reason, deposit: 0 })
+        );
+        assert_eq!(StorageVersion::get::<PoeModule>(), 7);
+    })
+}
+
+#[test]
+fn migration_marks_unknown_creation_times() {
+    new_test_ext().execute_with(|| {
+        use crate::migrations::v3::V3ProofInfo;
+        use frame_support::{storage::migration::put_storage_value, traits::StorageVersion};
+
+        StorageVersion::new(6).put::<PoeModule>();
+        let other = Claim { digest: [2; 32], ..CLAIM };
+        for &(claim, created_at) in &[(CLAIM, 0u64), (other, 42)] {
+            let old = V3ProofInfo { owner: 1u64, block_number: 5u64, created_at, depositor: 1u64, deposit: 0u128 };
+            put_storage_value(b"PoeModule", b"Proofs", &Blake2_128Concat::hash(&claim.encode()), old);
+        }
+
+        crate::migrations::migrate::<Test>();
+
+        assert_eq!(Proofs::<Test>::get(&CLAIM).unwrap().created_at, None);
+        assert_eq!(Proofs::<Test>::get(&other).unwrap().created_at, Some(42));
+        assert_eq!(StorageVersion::get::<PoeModule>(), 7);
+    })
+}
+
+/// Stands in for `ChargeTransactionPayment` of an account that cannot pay.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug)]
+struct CannotPay;
+
+impl sp_runtime::traits::SignedExtension for CannotPay {
+    const IDENTIFIER: &'static str = "CannotPay";
+    type AccountId = u64;
+    type Call = crate::mock::Call;
+    type AdditionalSigned = ();
+    type Pre = ();
+
+    fn additional_signed(&self) -> Result<(), sp_runtime::transaction_validity::TransactionValidityError> {
+        Ok(())
+    }
+
+    fn validate(
+        &self,
+        _: &u64,
+        _: &crate::mock::Call,
+        _: &sp_runtime::traits::DispatchInfoOf<crate::mock::Call>,
+        _: usize,
+    ) -> sp_runtime::transaction_validity::TransactionValidity {
+        Err(sp_runtime::transaction_validity::InvalidTransaction::Payment.into())
+    }
+}
+
+#[test]
+fn free_proofs_are_limited_per_era() {
+    use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction};
+
+    new_test_ext().execute_with(|| {
+        let slot = |slot| FreeProofs::<Test, _>::new(CannotPay, slot);
+        let info = Default::default();
+        let create = |digest| crate::mock::Call::PoeModule(crate::Call::create_proof(None, ALGORITHM, digest, None, None));
+        let revoke = crate::mock::Call::PoeModule(crate::Call::revoke_proof(None, ALGORITHM, DIGEST, RevocationReason::Superseded));
+
+        // An account can queue its whole quota; each slot waits for the one before it.
+        let first = slot(0).validate(&1, &create([1; 32]), &info, 0).unwrap();
+        let second = slot(1).validate(&1, &create([2; 32]), &info, 0).unwrap();
+        assert!(first.requires.is_empty());
+        assert_eq!(second.requires, first.provides);
+        assert_ne!(first.provides, second.provides);
+        assert_eq!(first.provides, slot(0).validate(&1, &create([2; 32]), &info, 0).unwrap().provides);
+        assert_ne!(first.provides, slot(0).validate(&2, &create([1; 32]), &info, 0).unwrap().provides);
+        assert_eq!(slot(0).validate(&1, &revoke, &info, 0), Err(InvalidTransaction::Payment.into()));
+
+        // FreeProofsPerEra is 2
+        assert_eq!(slot(2).validate(&1, &create([3; 32]), &info, 0), Err(InvalidTransaction::Payment.into()));
+        assert_eq!(slot(1).pre_dispatch(&1, &create([2; 32]), &info, 0), Err(InvalidTransaction::Future.into()));
+        assert_ok!(slot(0).pre_dispatch(&1, &create([1; 32]), &info, 0));
+        assert!(slot(1).validate(&1, &create([2; 32]), &info, 0).unwrap().requires.is_empty());
+        assert_eq!(slot(0).validate(&1, &create([3; 32]), &info, 0), Err(InvalidTransaction::Payment.into()));
+        assert_ok!(slot(1).pre_dispatch(&1, &create([2; 32]), &info, 0));
+        assert_eq!(
+            slot(1).pre_dispatch(&1, &create([3; 32]), &info, 0),
+            Err(InvalidTransaction::Payment.into())
+        );
+        assert_eq!(PoeModule::free_proofs_used(1), (0, 2));
+
+        // FreeProofEraLength is 10
+        System::set_block_number(10);
+        assert_ok!(slot(0).validate(&1, &create([3; 32]), &info, 0));
+        assert_ok!(slot(0).pre_dispatch(&1, &create([3; 32]), &info, 0));
+        assert_eq!(PoeModule::free_proofs_used(1), (1, 1));
     })
 }
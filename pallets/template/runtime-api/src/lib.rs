@@ -0,0 +1,20 @@
+//! Runtime API definition for the template pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	pub trait TemplateApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The value `who` stores under `key`.
+		fn value(who: AccountId, key: Vec<u8>) -> Option<Vec<u8>>;
+		/// Every key and value `who` stores.
+		fn values(who: AccountId) -> Vec<(Vec<u8>, Vec<u8>)>;
+		/// Earlier values `who` stored under `key`, oldest first, with the block each was set in.
+		fn history(who: AccountId, key: Vec<u8>) -> Vec<(BlockNumber, Vec<u8>)>;
+	}
+}
@@ -5,15 +5,82 @@ use super::*;
 #[allow(unused)]
 use crate::Pallet as Template;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::traits::Currency;
 use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::{convert::TryFrom, vec};
+
+fn funded_caller<T: Config>() -> T::AccountId {
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	caller
+}
+
+fn key<T: Config>(byte: u8) -> KeyOf<T> {
+	KeyOf::<T>::try_from(vec![byte; T::MaxKeyLength::get() as usize]).unwrap()
+}
+
+fn value<T: Config>(byte: u8) -> ValueOf<T> {
+	sized_value::<T>(byte, T::MaxValueLength::get())
+}
+
+fn sized_value<T: Config>(byte: u8, len: u32) -> ValueOf<T> {
+	ValueOf::<T>::try_from(vec![byte; len as usize]).unwrap()
+}
+
+/// Sets `key` for `who` until it has `history` old values.
+fn store_with_history<T: Config>(who: &T::AccountId, key: &KeyOf<T>, history: u32) {
+	for i in 0..=history {
+		Template::<T>::set_value(RawOrigin::Signed(who.clone()).into(), key.clone(), value::<T>(i as u8))
+			.unwrap();
+	}
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
-		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+	set_parameter {
+		let value: T::Balance = 100u32.into();
+	}: _(RawOrigin::Root, ParameterKey::PledgeQuantity, Some(value))
+	verify {
+		assert_eq!(Parameters::<T>::get(ParameterKey::PledgeQuantity), Some(value));
+	}
+
+	set_value_new {
+		let v in 0 .. T::MaxValueLength::get();
+		let caller = funded_caller::<T>();
+		// All but one of the caller's slots are taken.
+		for i in 1..T::MaxItems::get() {
+			let other = KeyOf::<T>::try_from(i.to_le_bytes().to_vec()).unwrap();
+			Template::<T>::set_value(RawOrigin::Signed(caller.clone()).into(), other, value::<T>(0))?;
+		}
+		let key = key::<T>(0xff);
+	}: set_value(RawOrigin::Signed(caller.clone()), key.clone(), sized_value::<T>(1, v))
+	verify {
+		assert_eq!(ItemCount::<T>::get(&caller), T::MaxItems::get());
+		assert_eq!(Items::<T>::get(&caller, &key).map(|item| item.value), Some(sized_value::<T>(1, v)));
+	}
+
+	set_value_existing {
+		let v in 0 .. T::MaxValueLength::get();
+		// With a full history the oldest value is dropped as well.
+		let h in 0 .. T::MaxHistoryLength::get();
+		let caller = funded_caller::<T>();
+		let key = key::<T>(0);
+		store_with_history::<T>(&caller, &key, h);
+	}: set_value(RawOrigin::Signed(caller.clone()), key.clone(), sized_value::<T>(0xff, v))
+	verify {
+		let expected = (h + 1).min(T::MaxHistoryLength::get());
+		assert_eq!(History::<T>::get(&caller, &key).len() as u32, expected);
+		assert_eq!(Items::<T>::get(&caller, &key).map(|item| item.value), Some(sized_value::<T>(0xff, v)));
+	}
+
+	remove_value {
+		let caller = funded_caller::<T>();
+		let key = key::<T>(0);
+		store_with_history::<T>(&caller, &key, T::MaxHistoryLength::get());
+	}: _(RawOrigin::Signed(caller.clone()), key.clone())
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(Items::<T>::get(&caller, &key).is_none());
+		assert!(History::<T>::get(&caller, &key).is_empty());
 	}
 }
 
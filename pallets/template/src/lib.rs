@@ -14,48 +14,160 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::{dispatch::DispatchResult, pallet_prelude::*};
+	use crate::WeightInfo;
+	use codec::{Decode, Encode, MaxEncodedLen};
+	use frame_support::{
+		dispatch::DispatchResult,
+		pallet_prelude::*,
+		storage::bounded_vec::BoundedVec,
+		traits::{Currency, ReservableCurrency},
+		CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+	};
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::{
+		traits::AtLeast32BitUnsigned,
+		RuntimeDebug,
+	};
+	use sp_std::{convert::TryFrom, marker::PhantomData, prelude::*};
+
+	/// The runtime parameters root can change without a runtime upgrade.
+	///
+	/// Two of the constants this registry was meant to replace have no key:
+	/// - `ProofLimit` no longer exists. `pallet_poe` keys proofs by a fixed 32-byte digest, so
+	///   there is no claim length to limit. Its storage cost is governed by `ProofDepositPerByte`.
+	/// - `ExistentialDeposit` stays a constant. Raising it would leave existing accounts below the
+	///   new minimum, and lowering it would change what `pallet_balances` reaps under accounts that
+	///   were created against the old value.
+	#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+	pub enum ParameterKey {
+		/// Deposit reserved per byte of a proof in `pallet_poe`.
+		ProofDepositPerByte,
+		/// Balance reserved for every new kitty, or kitty changing hands, in `pallet_kitties`.
+		PledgeQuantity,
+		/// Transaction fee per byte.
+		TransactionByteFee,
+	}
+
+	/// A value an account stores, and the deposit it reserved for it.
+	#[derive(
+		Encode, Decode, MaxEncodedLen, CloneNoBound, PartialEqNoBound, EqNoBound, RuntimeDebugNoBound,
+	)]
+	pub struct Item<BlockNumber, Balance, MaxValueLength>
+	where
+		BlockNumber: Clone + PartialEq + Eq + core::fmt::Debug,
+		Balance: Clone + PartialEq + Eq + core::fmt::Debug,
+		MaxValueLength: Get<u32>,
+	{
+		pub value: BoundedVec<u8, MaxValueLength>,
+		/// The block the value was set in.
+		pub set_at: BlockNumber,
+		pub deposit: Balance,
+	}
 
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+		/// The type of every parameter value.
+		type Balance: Parameter + Member + AtLeast32BitUnsigned + Default + Copy + MaxEncodedLen;
+		/// The currency item deposits are reserved in.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Deposit reserved for every item an account stores, history included.
+		#[pallet::constant]
+		type ItemDeposit: Get<BalanceOf<Self>>;
+		/// Maximum number of items an account can store.
+		#[pallet::constant]
+		type MaxItems: Get<u32>;
+		#[pallet::constant]
+		type MaxKeyLength: Get<u32>;
+		#[pallet::constant]
+		type MaxValueLength: Get<u32>;
+		/// Number of earlier values kept for every item. The oldest is dropped first.
+		#[pallet::constant]
+		type MaxHistoryLength: Get<u32>;
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	pub type KeyOf<T> = BoundedVec<u8, <T as Config>::MaxKeyLength>;
+
+	pub type ValueOf<T> = BoundedVec<u8, <T as Config>::MaxValueLength>;
+
+	pub type ItemOf<T> =
+		Item<<T as frame_system::Config>::BlockNumber, BalanceOf<T>, <T as Config>::MaxValueLength>;
+
+	pub type HistoryOf<T> = BoundedVec<
+		(<T as frame_system::Config>::BlockNumber, ValueOf<T>),
+		<T as Config>::MaxHistoryLength,
+	>;
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::generate_storage_info]
 	pub struct Pallet<T>(_);
 
 	// The pallet's runtime storage items.
 	// https://substrate.dev/docs/en/knowledgebase/runtime/storage
+	/// Parameters root has set. Unset parameters keep the default the runtime gives
+	/// [`ParameterOf`].
 	#[pallet::storage]
-	#[pallet::getter(fn something)]
-	// Learn more about declaring storage items:
-	// https://substrate.dev/docs/en/knowledgebase/runtime/storage#declaring-storage-items
-	pub type Something<T> = StorageValue<_, u32>;
+	#[pallet::getter(fn parameters)]
+	pub type Parameters<T: Config> = StorageMap<_, Twox64Concat, ParameterKey, T::Balance>;
+
+	/// The items of every account.
+	#[pallet::storage]
+	#[pallet::getter(fn items)]
+	pub type Items<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, KeyOf<T>, ItemOf<T>>;
+
+	/// Number of items every account stores.
+	#[pallet::storage]
+	#[pallet::getter(fn item_count)]
+	pub type ItemCount<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	/// Earlier values of every item, oldest first, with the block each was set in.
+	#[pallet::storage]
+	#[pallet::getter(fn history)]
+	pub type History<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		KeyOf<T>,
+		HistoryOf<T>,
+		ValueQuery,
+	>;
 
 	// Pallets use events to inform users when important changes are made.
 	// https://substrate.dev/docs/en/knowledgebase/runtime/events
 	#[pallet::event]
-	#[pallet::metadata(T::AccountId = "AccountId")]
+	#[pallet::metadata(T::AccountId = "AccountId", T::Balance = "Balance")]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Event documentation should end with an array that provides descriptive names for event
-		/// parameters. [something, who]
-		SomethingStored(u32, T::AccountId),
+		/// A parameter was set, or reset to its default with `None`. [key, value]
+		ParameterSet(ParameterKey, Option<T::Balance>),
+		/// [who, key, value]
+		ValueSet(T::AccountId, KeyOf<T>, ValueOf<T>),
+		/// [who, key]
+		ValueRemoved(T::AccountId, KeyOf<T>),
 	}
 
 	// Errors inform users that something went wrong.
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Error names should be descriptive.
-		NoneValue,
-		/// Errors should have helpful documentation associated with them.
-		StorageOverflow,
+		/// The account already stores `MaxItems` items.
+		TooManyItems,
+		/// The account stores nothing under this key.
+		ItemNotFound,
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -63,41 +175,111 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
-			// This function will return an error if the extrinsic is not signed.
-			// https://substrate.dev/docs/en/knowledgebase/runtime/origin
-			let who = ensure_signed(origin)?;
+		/// Sets the parameter `key`, or with `None` resets it to its default. Root only.
+		#[pallet::weight(T::WeightInfo::set_parameter())]
+		pub fn set_parameter(
+			origin: OriginFor<T>,
+			key: ParameterKey,
+			value: Option<T::Balance>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
 
-			// Update storage.
-			<Something<T>>::put(something);
+			Parameters::<T>::set(key, value);
 
-			// Emit an event.
-			Self::deposit_event(Event::SomethingStored(something, who));
-			// Return a successful DispatchResultWithPostInfo
+			Self::deposit_event(Event::ParameterSet(key, value));
 			Ok(())
 		}
 
-		/// An example dispatchable that may throw a custom error.
-		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1,1))]
-		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
+		/// Stores `value` under `key` for the caller. A new key reserves `ItemDeposit`; an
+		/// existing one moves its current value to the history.
+		#[pallet::weight({
+			let v = value.len() as u32;
+			T::WeightInfo::set_value_new(v)
+				.max(T::WeightInfo::set_value_existing(v, T::MaxHistoryLength::get()))
+		})]
+		pub fn set_value(origin: OriginFor<T>, key: KeyOf<T>, value: ValueOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
 
-			// Read a value from storage.
-			match <Something<T>>::get() {
-				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue)?,
+			let deposit = match Items::<T>::get(&who, &key) {
 				Some(old) => {
-					// Increment the value read from storage; will error in the event of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
-					// Update the value in storage with the incremented result.
-					<Something<T>>::put(new);
-					Ok(())
+					History::<T>::mutate(&who, &key, |history| {
+						if !history.is_empty() && history.len() as u32 >= T::MaxHistoryLength::get() {
+							history.remove(0);
+						}
+						// Only fails if `MaxHistoryLength` is zero, which keeps no history.
+						let _ = history.try_push((old.set_at, old.value));
+					});
+					old.deposit
 				},
-			}
+				None => {
+					let count = ItemCount::<T>::get(&who);
+					ensure!(count < T::MaxItems::get(), Error::<T>::TooManyItems);
+					let deposit = T::ItemDeposit::get();
+					T::Currency::reserve(&who, deposit)?;
+					ItemCount::<T>::insert(&who, count + 1);
+					deposit
+				},
+			};
+			Items::<T>::insert(&who, &key, Item { value: value.clone(), set_at: now, deposit });
+
+			Self::deposit_event(Event::ValueSet(who, key, value));
+			Ok(())
+		}
+
+		/// Removes the caller's item under `key` with its history, and returns its deposit.
+		#[pallet::weight(T::WeightInfo::remove_value())]
+		pub fn remove_value(origin: OriginFor<T>, key: KeyOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let item = Items::<T>::take(&who, &key).ok_or(Error::<T>::ItemNotFound)?;
+
+			History::<T>::remove(&who, &key);
+			ItemCount::<T>::mutate(&who, |count| *count = count.saturating_sub(1));
+			T::Currency::unreserve(&who, item.deposit);
+
+			Self::deposit_event(Event::ValueRemoved(who, key));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The value `who` stores under `key`.
+		pub fn value_of(who: &T::AccountId, key: Vec<u8>) -> Option<Vec<u8>> {
+			let key = KeyOf::<T>::try_from(key).ok()?;
+			Items::<T>::get(who, key).map(|item| item.value.into_inner())
+		}
+
+		/// Every key and value `who` stores.
+		pub fn values_of(who: &T::AccountId) -> Vec<(Vec<u8>, Vec<u8>)> {
+			Items::<T>::iter_prefix(who)
+				.map(|(key, item)| (key.into_inner(), item.value.into_inner()))
+				.collect()
+		}
+
+		/// Earlier values `who` stored under `key`, oldest first.
+		pub fn history_of(who: &T::AccountId, key: Vec<u8>) -> Vec<(T::BlockNumber, Vec<u8>)> {
+			KeyOf::<T>::try_from(key)
+				.map(|key| {
+					History::<T>::get(who, key)
+						.into_iter()
+						.map(|(set_at, value)| (set_at, value.into_inner()))
+						.collect()
+				})
+				.unwrap_or_default()
+		}
+	}
+
+	/// A `Get` for the parameter `K`, which is `D` until root sets it.
+	pub struct ParameterOf<T, K, D>(PhantomData<(T, K, D)>);
+
+	impl<T, K, D> Get<T::Balance> for ParameterOf<T, K, D>
+	where
+		T: Config,
+		K: Get<ParameterKey>,
+		D: Get<T::Balance>,
+	{
+		fn get() -> T::Balance {
+			Parameters::<T>::get(K::get()).unwrap_or_else(D::get)
 		}
 	}
 }
@@ -18,6 +18,7 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -25,8 +26,20 @@ frame_support::construct_runtime!(
 parameter_types! {
 	pub const BlockHashCount: u64 = 250;
 	pub const SS58Prefix: u8 = 42;
+	pub const PledgeQuantityKey: pallet_template::ParameterKey =
+		pallet_template::ParameterKey::PledgeQuantity;
+	pub const DefaultPledgeQuantity: u128 = 100;
+	pub const ExistentialDeposit: u128 = 1;
+	pub const ItemDeposit: u128 = 10;
+	pub const MaxItems: u32 = 2;
+	pub const MaxKeyLength: u32 = 8;
+	pub const MaxValueLength: u32 = 16;
+	pub const MaxHistoryLength: u32 = 2;
 }
 
+pub type PledgeQuantity =
+	pallet_template::ParameterOf<Test, PledgeQuantityKey, DefaultPledgeQuantity>;
+
 impl system::Config for Test {
 	type BaseCallFilter = frame_support::traits::AllowAll;
 	type BlockWeights = ();
@@ -45,7 +58,7 @@ impl system::Config for Test {
 	type BlockHashCount = BlockHashCount;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u128>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -53,11 +66,35 @@ impl system::Config for Test {
 	type OnSetCode = ();
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u128;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
 impl pallet_template::Config for Test {
 	type Event = Event;
+	type Balance = u128;
+	type Currency = Balances;
+	type ItemDeposit = ItemDeposit;
+	type MaxItems = MaxItems;
+	type MaxKeyLength = MaxKeyLength;
+	type MaxValueLength = MaxValueLength;
+	type MaxHistoryLength = MaxHistoryLength;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 15)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	t.into()
 }
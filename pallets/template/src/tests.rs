@@ -1,20 +1,148 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{mock::*, Error, KeyOf, ParameterKey, ValueOf};
+use frame_support::{assert_noop, assert_ok, traits::Get, weights::GetDispatchInfo};
+use sp_runtime::traits::BadOrigin;
+use sp_std::convert::TryFrom;
+
+fn key(key: &[u8]) -> KeyOf<Test> {
+	KeyOf::<Test>::try_from(key.to_vec()).unwrap()
+}
+
+fn value(value: &[u8]) -> ValueOf<Test> {
+	ValueOf::<Test>::try_from(value.to_vec()).unwrap()
+}
+
+#[test]
+fn root_sets_parameters() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Unset parameters read as their default.
+		assert_eq!(PledgeQuantity::get(), 100);
+
+		assert_ok!(TemplateModule::set_parameter(Origin::root(), ParameterKey::PledgeQuantity, Some(42)));
+		assert_eq!(TemplateModule::parameters(ParameterKey::PledgeQuantity), Some(42));
+		assert_eq!(PledgeQuantity::get(), 42);
+		System::assert_last_event(Event::TemplateModule(crate::Event::ParameterSet(
+			ParameterKey::PledgeQuantity,
+			Some(42),
+		)));
+
+		// `None` goes back to the default.
+		assert_ok!(TemplateModule::set_parameter(Origin::root(), ParameterKey::PledgeQuantity, None));
+		assert_eq!(PledgeQuantity::get(), 100);
+		System::assert_last_event(Event::TemplateModule(crate::Event::ParameterSet(
+			ParameterKey::PledgeQuantity,
+			None,
+		)));
+	});
+}
+
+#[test]
+fn signed_origin_cannot_set_parameters() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::set_parameter(Origin::signed(1), ParameterKey::PledgeQuantity, Some(42)),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn set_value_reserves_a_deposit_per_key() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::set_value(Origin::signed(1), key(b"name"), value(b"alice")));
+		assert_eq!(TemplateModule::value_of(&1, b"name".to_vec()), Some(b"alice".to_vec()));
+		assert_eq!(TemplateModule::item_count(1), 1);
+		assert_eq!(Balances::reserved_balance(1), 10);
+		System::assert_last_event(Event::TemplateModule(crate::Event::ValueSet(
+			1,
+			key(b"name"),
+			value(b"alice"),
+		)));
+
+		// Overwriting keeps the deposit.
+		assert_ok!(TemplateModule::set_value(Origin::signed(1), key(b"name"), value(b"bob")));
+		assert_eq!(TemplateModule::value_of(&1, b"name".to_vec()), Some(b"bob".to_vec()));
+		assert_eq!(TemplateModule::item_count(1), 1);
+		assert_eq!(Balances::reserved_balance(1), 10);
+
+		// Keys are per account.
+		assert_eq!(TemplateModule::value_of(&2, b"name".to_vec()), None);
+	});
+}
+
+#[test]
+fn history_keeps_the_latest_values() {
+	new_test_ext().execute_with(|| {
+		for (block, v) in [b"one", b"two", b"six", b"ten"].iter().enumerate() {
+			System::set_block_number(block as u64 + 1);
+			assert_ok!(TemplateModule::set_value(Origin::signed(1), key(b"k"), value(*v)));
+		}
+
+		// `MaxHistoryLength` is 2, so `one` is gone.
+		assert_eq!(
+			TemplateModule::history_of(&1, b"k".to_vec()),
+			vec![(2, b"two".to_vec()), (3, b"six".to_vec())]
+		);
+		assert_eq!(TemplateModule::items(1, key(b"k")).map(|item| item.set_at), Some(4));
+	});
+}
+
+#[test]
+fn remove_value_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::set_value(Origin::signed(1), key(b"k"), value(b"a")));
+		assert_ok!(TemplateModule::set_value(Origin::signed(1), key(b"k"), value(b"b")));
+
+		assert_ok!(TemplateModule::remove_value(Origin::signed(1), key(b"k")));
+		assert_eq!(TemplateModule::value_of(&1, b"k".to_vec()), None);
+		assert!(TemplateModule::history_of(&1, b"k".to_vec()).is_empty());
+		assert_eq!(TemplateModule::item_count(1), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		System::assert_last_event(Event::TemplateModule(crate::Event::ValueRemoved(1, key(b"k"))));
+
+		assert_noop!(
+			TemplateModule::remove_value(Origin::signed(1), key(b"k")),
+			Error::<Test>::ItemNotFound
+		);
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn accounts_cannot_exceed_max_items() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_ok!(TemplateModule::set_value(Origin::signed(1), key(b"a"), value(b"1")));
+		assert_ok!(TemplateModule::set_value(Origin::signed(1), key(b"b"), value(b"2")));
+		assert_noop!(
+			TemplateModule::set_value(Origin::signed(1), key(b"c"), value(b"3")),
+			Error::<Test>::TooManyItems
+		);
+
+		// Existing keys can still change.
+		assert_ok!(TemplateModule::set_value(Origin::signed(1), key(b"a"), value(b"3")));
+		let mut values = TemplateModule::values_of(&1);
+		values.sort();
+		assert_eq!(values, vec![(b"a".to_vec(), b"3".to_vec()), (b"b".to_vec(), b"2".to_vec())]);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn set_value_needs_the_deposit() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_ok!(TemplateModule::set_value(Origin::signed(2), key(b"a"), value(b"1")));
+		// 5 left, below `ItemDeposit`.
+		assert_noop!(
+			TemplateModule::set_value(Origin::signed(2), key(b"b"), value(b"2")),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
 	});
 }
+
+#[test]
+fn set_value_weight_grows_with_the_value() {
+	let weight = |bytes: &[u8]| {
+		crate::Call::<Test>::set_value(key(b"k"), value(bytes)).get_dispatch_info().weight
+	};
+	assert!(weight(&[0; 16]) > weight(&[0; 1]));
+}
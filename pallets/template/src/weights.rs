@@ -0,0 +1,89 @@
+//! Weights for pallet_template.
+//!
+//! Each weight is a base execution time, terms for the value length `v` and the history length
+//! `h` where the call's work grows with them, and the storage reads and writes priced with the
+//! runtime's `DbWeight`. The execution terms are estimates until measured: regenerate this file
+//! from the benchmarks in `benchmarking.rs` with a node built with `--features runtime-benchmarks`:
+//! `./target/release/node-template benchmark --chain dev --execution wasm --wasm-execution compiled
+//! --pallet pallet_template --extrinsic '*' --steps 50 --repeat 20 --output
+//! pallets/template/src/weights.rs`
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_template.
+pub trait WeightInfo {
+	fn set_parameter() -> Weight;
+	fn set_value_new(v: u32) -> Weight;
+	fn set_value_existing(v: u32, h: u32) -> Weight;
+	fn remove_value() -> Weight;
+}
+
+/// Weights for pallet_template using the node template's database weights.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: TemplateModule Parameters (r:0 w:1)
+	fn set_parameter() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: TemplateModule Items (r:1 w:1)
+	// Storage: TemplateModule ItemCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn set_value_new(v: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: TemplateModule Items (r:1 w:1)
+	// Storage: TemplateModule History (r:1 w:1)
+	fn set_value_existing(v: u32, h: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((600_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: TemplateModule Items (r:1 w:1)
+	// Storage: TemplateModule History (r:0 w:1)
+	// Storage: TemplateModule ItemCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn remove_value() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	fn set_parameter() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_value_new(v: u32, ) -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_value_existing(v: u32, h: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((2_000 as Weight).saturating_mul(v as Weight))
+			.saturating_add((600_000 as Weight).saturating_mul(h as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn remove_value() -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+}
@@ -15,7 +15,7 @@ use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
-	transaction_validity::{TransactionSource, TransactionValidity},
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
 use sp_std::prelude::*;
@@ -42,8 +42,10 @@ pub use sp_runtime::{Perbill, Permill};
 
 /// Import the template pallet.
 pub use pallet_template;
+use pallet_template::{ParameterKey, ParameterOf};
 pub use pallet_poe;
 pub use pallet_kitties;
+pub use pallet_battles;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -61,6 +63,9 @@ pub type Balance = u128;
 /// Index of a transaction in the chain.
 pub type Index = u32;
 
+/// A timestamp: milliseconds since the unix epoch.
+pub type Moment = u64;
+
 /// A hash of some data used by the chain.
 pub type Hash = sp_core::H256;
 
@@ -225,8 +230,7 @@ parameter_types! {
 }
 
 impl pallet_timestamp::Config for Runtime {
-	/// A timestamp: milliseconds since the unix epoch.
-	type Moment = u64;
+	type Moment = Moment;
 	type OnTimestampSet = Aura;
 	type MinimumPeriod = MinimumPeriod;
 	type WeightInfo = ();
@@ -235,11 +239,12 @@ impl pallet_timestamp::Config for Runtime {
 parameter_types! {
 	pub const ExistentialDeposit: u128 = 500;
 	pub const MaxLocks: u32 = 50;
+	pub const MaxReserves: u32 = 50;
 }
 
 impl pallet_balances::Config for Runtime {
 	type MaxLocks = MaxLocks;
-	type MaxReserves = ();
+	type MaxReserves = MaxReserves;
 	type ReserveIdentifier = [u8; 8];
 	/// The type for recording an account's balance.
 	type Balance = Balance;
@@ -252,9 +257,13 @@ impl pallet_balances::Config for Runtime {
 }
 
 parameter_types! {
-	pub const TransactionByteFee: Balance = 1;
+	pub const TransactionByteFeeKey: ParameterKey = ParameterKey::TransactionByteFee;
+	pub const DefaultTransactionByteFee: Balance = 1;
 }
 
+pub type TransactionByteFee =
+	ParameterOf<Runtime, TransactionByteFeeKey, DefaultTransactionByteFee>;
+
 impl pallet_transaction_payment::Config for Runtime {
 	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
 	type TransactionByteFee = TransactionByteFee;
@@ -267,30 +276,120 @@ impl pallet_sudo::Config for Runtime {
 	type Call = Call;
 }
 
+parameter_types! {
+	pub const TemplateItemDeposit: Balance = 1_000;
+	pub const MaxTemplateItems: u32 = 32;
+	pub const MaxTemplateKeyLength: u32 = 32;
+	pub const MaxTemplateValueLength: u32 = 256;
+	pub const MaxTemplateHistoryLength: u32 = 8;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type Event = Event;
+	type Balance = Balance;
+	type Currency = Balances;
+	type ItemDeposit = TemplateItemDeposit;
+	type MaxItems = MaxTemplateItems;
+	type MaxKeyLength = MaxTemplateKeyLength;
+	type MaxValueLength = MaxTemplateValueLength;
+	type MaxHistoryLength = MaxTemplateHistoryLength;
+	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
 }
 
 impl pallet_poe::Config for Runtime {
 	type Event = Event;
-	type ProofLimit = ProofLimit;
+	type Currency = Balances;
+	type DepositPerByte = ProofDepositPerByte;
+	type DepositMovesWithProof = ProofDepositMovesWithProof;
+	type Time = Timestamp;
+	type MaxHistoryLength = MaxProofHistoryLength;
+	type MaxMetadataLength = MaxProofMetadataLength;
+	type MaxExpiringPerBlock = MaxProofsExpiringPerBlock;
+	type MaxAttesters = MaxProofAttesters;
+	type MaxNamespaceMembers = MaxProofNamespaceMembers;
+	type MaxReasonLength = MaxProofRevocationReasonLength;
+	type AllowReregistration = AllowProofReregistration;
+	type MaxDocumentSize = MaxProofDocumentSize;
+	type DocumentFeePerByte = ProofDocumentFeePerByte;
+	type DocumentFee = ();
+	type FreeProofsPerEra = FreeProofsPerEra;
+	type FreeProofEraLength = FreeProofEraLength;
+	type OffchainSignature = Signature;
+	type SigningPublicKey = <Signature as Verify>::Signer;
 }
 
 parameter_types! {
-	pub const ProofLimit: u32 = 128;
+	pub const ProofDepositPerByteKey: ParameterKey = ParameterKey::ProofDepositPerByte;
+	pub const DefaultProofDepositPerByte: Balance = 10;
+	pub const ProofDepositMovesWithProof: bool = true;
+	pub const MaxProofHistoryLength: u32 = 32;
+	pub const MaxProofMetadataLength: u32 = 256;
+	pub const MaxProofsExpiringPerBlock: u32 = 64;
+	pub const MaxProofAttesters: u32 = 16;
+	pub const MaxProofNamespaceMembers: u32 = 64;
+	pub const MaxProofRevocationReasonLength: u32 = 128;
+	pub const AllowProofReregistration: bool = false;
+	pub const MaxProofDocumentSize: u32 = 256 * 1024;
+	pub const ProofDocumentFeePerByte: Balance = 100;
+	pub const FreeProofsPerEra: u32 = 3;
+	pub const FreeProofEraLength: BlockNumber = DAYS;
 }
 
+pub type ProofDepositPerByte =
+	ParameterOf<Runtime, ProofDepositPerByteKey, DefaultProofDepositPerByte>;
+
 impl pallet_kitties::Config for Runtime {
 	type Event = Event;
 	type Randomness = RandomnessCollectiveFlip;
 	type KittyIndex = u32;
 	type Currency = Balances;
 	type PledgeQuantity = PledgeQuantity;
+	type CollectionId = u32;
+	type MaxCollectionNameLength = MaxCollectionNameLength;
+	type MaxHistoryLength = MaxHistoryLength;
+	type VolumeBucketLength = VolumeBucketLength;
+	type VolumeBuckets = VolumeBuckets;
+	type ListingTtl = ListingTtl;
+	type UnsignedPriority = KittiesUnsignedPriority;
+}
+
+parameter_types! {
+	pub const PledgeQuantityKey: ParameterKey = ParameterKey::PledgeQuantity;
+	pub const DefaultPledgeQuantity: u128 = 10000;
+	pub const MaxCollectionNameLength: u32 = 64;
+	pub const MaxHistoryLength: u32 = 32;
+	pub const VolumeBucketLength: BlockNumber = HOURS;
+	pub const VolumeBuckets: u32 = 24;
+	pub const ListingTtl: BlockNumber = 7 * DAYS;
+	pub const KittiesUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+pub type PledgeQuantity = ParameterOf<Runtime, PledgeQuantityKey, DefaultPledgeQuantity>;
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	Call: From<C>,
+{
+	type Extrinsic = UncheckedExtrinsic;
+	type OverarchingCall = Call;
+}
+
+impl pallet_battles::Config for Runtime {
+	type Event = Event;
+	type KittyIndex = u32;
+	type Kitties = KittiesModule;
+	type Randomness = RandomnessCollectiveFlip;
+	type Currency = Balances;
+	type BattleStake = BattleStake;
+	type SettlementDelay = SettlementDelay;
+	type MaxBattlesPerBlock = MaxBattlesPerBlock;
 }
 
 parameter_types! {
-	pub const PledgeQuantity: u128 = 10000;
+	pub const BattleStake: u128 = 1000;
+	pub const SettlementDelay: BlockNumber = 2;
+	pub const MaxBattlesPerBlock: u32 = 32;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -311,7 +410,8 @@ construct_runtime!(
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
 		PoeModule: pallet_poe::{Pallet, Call, Storage, Event<T>},
-		KittiesModule: pallet_kitties::{Pallet, Call, Storage, Event<T>},
+		KittiesModule: pallet_kitties::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
+		BattlesModule: pallet_battles::{Pallet, Call, Storage, Event<T>},
 	}
 );
 
@@ -329,7 +429,7 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_poe::FreeProofs<Runtime, pallet_transaction_payment::ChargeTransactionPayment<Runtime>>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
@@ -469,6 +569,65 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_rpc_runtime_api::KittiesApi<Block, u32, AccountId, Balance, BlockNumber> for Runtime {
+		fn provenance(
+			kitty_id: u32,
+		) -> Vec<pallet_kitties::OwnershipRecord<AccountId, Balance, BlockNumber>> {
+			KittiesModule::provenance(kitty_id)
+		}
+
+		fn market_stats() -> pallet_kitties::MarketStats<Balance> {
+			KittiesModule::market_stats()
+		}
+	}
+
+	impl pallet_template_runtime_api::TemplateApi<Block, AccountId, BlockNumber> for Runtime {
+		fn value(who: AccountId, key: Vec<u8>) -> Option<Vec<u8>> {
+			TemplateModule::value_of(&who, key)
+		}
+
+		fn values(who: AccountId) -> Vec<(Vec<u8>, Vec<u8>)> {
+			TemplateModule::values_of(&who)
+		}
+
+		fn history(who: AccountId, key: Vec<u8>) -> Vec<(BlockNumber, Vec<u8>)> {
+			TemplateModule::history_of(&who, key)
+		}
+	}
+
+	impl pallet_poe_rpc_runtime_api::PoeApi<Block, AccountId, BlockNumber, Balance, Moment, Signature> for Runtime {
+		fn get_proof(
+			claim: pallet_poe::Claim,
+		) -> Option<pallet_poe::ProofInfo<AccountId, BlockNumber, Balance, Moment>> {
+			PoeModule::proofs(claim)
+		}
+
+		fn proofs_of(
+			owner: AccountId,
+			page: u32,
+		) -> Vec<(pallet_poe::Claim, pallet_poe::ProofInfo<AccountId, BlockNumber, Balance, Moment>)> {
+			PoeModule::proofs_of(owner, page)
+		}
+
+		fn count() -> u32 {
+			PoeModule::proof_count()
+		}
+
+		fn verify_inclusion(
+			namespace: Option<pallet_poe::NamespaceId>,
+			algorithm: pallet_poe::HashAlgorithm,
+			root: [u8; 32],
+			leaf: [u8; 32],
+			path: Vec<[u8; 32]>,
+		) -> Option<pallet_poe::ProofInfo<AccountId, BlockNumber, Balance, Moment>> {
+			PoeModule::verify_inclusion(namespace, algorithm, root, leaf, path)
+		}
+
+		fn check_signature(claim: pallet_poe::Claim, owner: AccountId, signature: Signature) -> bool {
+			PoeModule::check_signature(&claim, &owner, &signature)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn dispatch_benchmark(